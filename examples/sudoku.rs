@@ -2,7 +2,7 @@
 //! rewritten with the use of ranged_integers library
 
 #![allow(incomplete_features)]
-#![feature(adt_const_params,generic_const_exprs)]
+#![feature(generic_const_exprs)]
 
 use ranged_integers::*;
 
@@ -16,28 +16,27 @@ pub fn is_valid(val: Val, x: RowIndex, y: RowIndex, sudoku_ar: &Sudoku) -> bool
     r!(0..=8).into_iter().all( |i|
         sudoku_ar[x][i] != val && 
         sudoku_ar[i][y] != val && {
-            r!(0..=2).into_iter().all(|i| r!(0..=2).into_iter().all(|j| 
+            r!(0..=2).cross(r!(0..=2)).all(|(i, j)|
                 sudoku_ar[x / r!(3) * r!(3) + i][y / r!(3) * r!(3) + j] != val
-            ))
+            )
         }
     )
 }
 
 pub fn place_number(pos: Ranged<0, 80>, sudoku_ar: &mut Sudoku) -> bool {
     pos.iter_up()
-        .find_map(|p| {
-                let (x, y) = (p % r!(9), p / r!(9));
-                if sudoku_ar[x][y] == r!(0) {Some((x,y))} else {None}
-            })
-        .is_none_or(|(x, y)| {
+        .map(Idx2::<RowIndex, RowIndex>::unflatten)
+        .find(|&idx| sudoku_ar[idx] == r!(0))
+        .is_none_or(|idx| {
+            let Idx2(x, y) = idx;
             for n in r!(1..=9) {
                 if is_valid(n.expand(), x, y, sudoku_ar) {
-                    sudoku_ar[x][y] = n.expand();
+                    sudoku_ar[idx] = n.expand();
                     let next = if let Some(next) = (pos + r!(1)).fit() {next} else {return true};
                     if place_number(next,sudoku_ar) {
                         return true;
                     }
-                    sudoku_ar[x][y] = r!([]0);
+                    sudoku_ar[idx] = r!([]0);
                 }
             }
             false
@@ -49,7 +48,7 @@ pub fn pretty_print(sudoku_ar: Sudoku) {
     println!("{line_sep}");
     for (i, row) in sudoku_ar.iter().enumerate() {
         for (j, val) in row.iter().enumerate() {
-            print!("{val} ");
+            print!("{} ", char::from(val.to_ascii_digit()));
             if j == 2 || j == 5 {
                 print!("| ");
             }
@@ -65,21 +64,27 @@ fn solve(sudoku_ar: &mut Sudoku)->bool {
     place_number(r!([] 0), sudoku_ar)
 }
 
-macro_rules! rangedarr {
-    ($($e:literal),* $(,)?) => {  [$( r!([] $e) ),*]  };
-} 
+const fn parse_row(row: &[u8; 9]) -> [Val; 9] {
+    let mut out = [r!([] 0); 9];
+    let mut i = 0;
+    while i < 9 {
+        out[i] = Val::from_ascii_digit(row[i]).expect("a digit");
+        i += 1;
+    }
+    out
+}
 
 fn main() {
     let mut sudoku_ar: Sudoku = [
-        rangedarr![8, 5, 0, 0, 0, 2, 4, 0, 0],
-        rangedarr![7, 2, 0, 0, 0, 0, 0, 0, 9],
-        rangedarr![0, 0, 4, 0, 0, 0, 0, 0, 0],
-        rangedarr![0, 0, 0, 1, 0, 7, 0, 0, 2],
-        rangedarr![3, 0, 5, 0, 0, 0, 9, 0, 0],
-        rangedarr![0, 4, 0, 0, 0, 0, 0, 0, 0],
-        rangedarr![0, 0, 0, 0, 8, 0, 0, 7, 0],
-        rangedarr![0, 1, 7, 0, 0, 0, 0, 0, 0],
-        rangedarr![0, 0, 0, 0, 3, 6, 0, 4, 0],
+        parse_row(b"850002400"),
+        parse_row(b"720000009"),
+        parse_row(b"004000000"),
+        parse_row(b"000107002"),
+        parse_row(b"305000900"),
+        parse_row(b"040000000"),
+        parse_row(b"000080070"),
+        parse_row(b"017000000"),
+        parse_row(b"000036040"),
     ];
 
     if solve(&mut sudoku_ar) {
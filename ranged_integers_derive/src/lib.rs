@@ -0,0 +1,118 @@
+//! Derive macro for [ranged_integers](https://crates.io/crates/ranged_integers)
+//!
+//! Do not use directly, enable the `derive` feature of `ranged_integers` and use
+//! `ranged_integers::RangedEnum` instead.
+
+#![deny(missing_docs)]
+#![warn(clippy::pedantic)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Literal, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Data, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Lit, UnOp, parse_macro_input, spanned::Spanned};
+
+/// Map a fieldless enum to and from `Ranged<MIN_DISCRIMINANT, MAX_DISCRIMINANT>`
+///
+/// Generates:
+/// - `From<Enum> for Ranged<MIN, MAX>`,
+/// - `From<Ranged<MIN, MAX>> for Enum` if the discriminants are contiguous, or
+///   `TryFrom<Ranged<MIN, MAX>> for Enum` returning the rejected value as the error otherwise,
+/// - `Enum::COUNT` constant holding the number of variants.
+///
+/// The explicit discriminants must be integer literals.
+#[proc_macro_derive(RangedEnum)]
+pub fn derive_ranged_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+// Evaluate an explicit discriminant: an integer literal, possibly negated
+fn discriminant_value(expr: &Expr) -> syn::Result<i128> {
+    match expr {
+        Expr::Lit(ExprLit { lit: Lit::Int(i), .. }) => i.base10_parse(),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => Ok(-discriminant_value(expr)?),
+        Expr::Group(g) => discriminant_value(&g.expr),
+        Expr::Paren(p) => discriminant_value(&p.expr),
+        _ => Err(syn::Error::new(expr.span(), "RangedEnum: the discriminant must be an integer literal")),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(input.span(), "RangedEnum: only enums are supported"));
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(input.generics.span(), "RangedEnum: generic enums are not supported"));
+    }
+    if data.variants.is_empty() {
+        return Err(syn::Error::new(input.span(), "RangedEnum: the enum must have variants"));
+    }
+
+    // Discriminants follow the Rust rules: explicit or previous+1, starting with 0
+    let mut variants = Vec::with_capacity(data.variants.len());
+    let mut next = 0_i128;
+    for v in &data.variants {
+        if !matches!(v.fields, Fields::Unit) {
+            return Err(syn::Error::new(v.span(), "RangedEnum: the variants must not have fields"));
+        }
+        let d = match &v.discriminant {
+            Some((_, expr)) => discriminant_value(expr)?,
+            None => next,
+        };
+        next = d + 1;
+        variants.push((&v.ident, d));
+    }
+
+    let min = variants.iter().map(|v| v.1).min().unwrap_or_default();
+    let max = variants.iter().map(|v| v.1).max().unwrap_or_default();
+    let count = variants.len();
+    // The discriminants are unique, so the contiguous ones fill the whole range
+    let contiguous = usize::try_from(max - min).is_ok_and(|d| d + 1 == count);
+
+    let (min, max) = (Literal::i128_unsuffixed(min), Literal::i128_unsuffixed(max));
+    let ranged = quote! { ::ranged_integers::Ranged<{#min}, {#max}> };
+    let idents = variants.iter().map(|v| v.0);
+    let values = variants.iter().map(|v| Literal::i128_unsuffixed(v.1));
+
+    let reverse = if contiguous {
+        quote! {
+            impl ::core::convert::From<#ranged> for #name {
+                fn from(r: #ranged) -> Self {
+                    match r.get() {
+                        #( #values => Self::#idents, )*
+                        _ => unsafe { ::core::hint::unreachable_unchecked() },
+                    }
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl ::core::convert::TryFrom<#ranged> for #name {
+                type Error = #ranged;
+                fn try_from(r: #ranged) -> ::core::result::Result<Self, Self::Error> {
+                    match r.get() {
+                        #( #values => ::core::result::Result::Ok(Self::#idents), )*
+                        _ => ::core::result::Result::Err(r),
+                    }
+                }
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #name {
+            /// The number of the enum variants
+            pub const COUNT: usize = #count;
+        }
+
+        impl ::core::convert::From<#name> for #ranged {
+            fn from(e: #name) -> Self {
+                unsafe { Self::unchecked_new(e as i128) }
+            }
+        }
+
+        #reverse
+    })
+}
@@ -0,0 +1,45 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+use ranged_integers::*;
+use ranged_integers_derive::RangedEnum;
+
+#[derive(RangedEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Month { Jan, Feb, Mar, Apr, May, Jun, Jul, Aug, Sep, Oct, Nov, Dec }
+
+#[derive(RangedEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(i8)]
+enum Level { Low = -1, Mid, High }
+
+#[derive(RangedEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Opcode { Nop = 0x10, Load = 0x20, Store, Halt = 0xFF }
+
+#[test]
+fn contiguous() {
+    let days = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+    assert_eq!(Month::COUNT, 12);
+    assert_eq!(days[Ranged::from(Month::Feb)], 28);
+
+    let r: Ranged<0, 11> = Month::Dec.into();
+    assert_eq!(r, r!(11));
+    assert_eq!(Month::from(r!([0 11] 2)), Month::Mar);
+    for i in r!(0..=11) {
+        assert_eq!(Ranged::from(Month::from(i)), i);
+    }
+
+    assert_eq!(Level::COUNT, 3);
+    let r: Ranged<-1, 1> = Level::Low.into();
+    assert_eq!(r, r!(-1));
+    assert_eq!(Level::from(r!([-1 1] 1)), Level::High);
+}
+
+#[test]
+fn sparse() {
+    assert_eq!(Opcode::COUNT, 4);
+    let r: Ranged<0x10, 0xFF> = Opcode::Store.into();
+    assert_eq!(r, r!(0x21));
+    assert_eq!(Opcode::try_from(r), Ok(Opcode::Store));
+    assert_eq!(Opcode::try_from(r!([0x10 0xFF] 0xFF)), Ok(Opcode::Halt));
+    assert_eq!(Opcode::try_from(r!([0x10 0xFF] 0x11)), Err(r!([] 0x11)));
+}
@@ -1,32 +1,199 @@
-use crate::{allow_range, conversions, irang, memlayout, Assert, ConstInclusiveRange, IsAllowed, Ranged};
+// Any `Ranged<MIN, MAX>` with `0 <= MIN` and `MAX < N` indexes `[T; N]` with no bounds check.
+// Note the generic bounds prevent the index type inference, so `arr[x.expand()]` or
+// `arr[r!([] 4)]` need the explicit bounds; use `arr[x]` or `arr[r!(4)]` instead.
+// The same rules apply to `Idx2` and `Idx3` indexing the arrays of arrays.
+//
+// Normalization bound. The items using the index type `Ranged<0, {N as irang - 1}>` of `[T; N]`
+// (here and in `Permutation` and `RingBuffer`) carry `Assert<{allow_range(memlayout(0, N as irang))}>`
+// besides the bound of the index type itself. It always holds for `N: usize`, but without it
+// the compiler fails to normalize the `{N as irang - 1}` expressions in the item bodies.
+// `BoundedVec` needs the same pair of bounds for its `Ranged<0, {CAP as irang}>` length.
+
+use core::marker::Destruct;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use crate::{allow_range, conversions, irang, memlayout, Assert, ConstInclusiveRange, ConstRange, IsAllowed, OperationPossibility, Ranged};
+use crate::value_check::allow_if;
 
 #[allow(clippy::cast_sign_loss)]
-impl<T, const N: usize> core::ops::Index<Ranged<0, {N as i128 - 1}>> for [T; N]
+impl<T, const N: usize, const MIN: irang, const MAX: irang> core::ops::Index<Ranged<MIN, MAX>> for [T; N]
 where
     // Constraint required by Ranged
-    Assert<{allow_range(memlayout(0, N as i128 - 1))}>: IsAllowed,
-    // Check if the range fits the array length
-    Assert<{conversions::converter_checkers::usize(0, N as i128 - 1)}>: IsAllowed
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    // Check if the range fits the array length (min)
+    Assert<{conversions::converter_checkers::usize(MIN, MAX)}>: IsAllowed,
+    // Check if the range fits the array length (max)
+    Assert<{conversions::lessthan(MAX, N as i128)}>: IsAllowed,
 {
     type Output = T;
-    fn index(&self, index: Ranged<0, {N as i128 - 1}>) -> &Self::Output {
+    fn index(&self, index: Ranged<MIN, MAX>) -> &Self::Output {
         unsafe{self.get_unchecked(index.usize())}
     }
 }
 
 #[allow(clippy::cast_sign_loss)]
-impl<T, const N: usize> core::ops::IndexMut<Ranged<0, {N as i128 - 1}>> for [T; N]
+impl<T, const N: usize, const MIN: irang, const MAX: irang> core::ops::IndexMut<Ranged<MIN, MAX>> for [T; N]
 where
     // Constraint required by Ranged
-    Assert<{allow_range(memlayout(0, N as i128 - 1))}>: IsAllowed,
-    // Check if the range fits the array length
-    Assert<{conversions::converter_checkers::usize(0, N as i128 - 1)}>: IsAllowed
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    // Check if the range fits the array length (min)
+    Assert<{conversions::converter_checkers::usize(MIN, MAX)}>: IsAllowed,
+    // Check if the range fits the array length (max)
+    Assert<{conversions::lessthan(MAX, N as i128)}>: IsAllowed,
 {
-    fn index_mut(&mut self, index: Ranged<0, {N as i128 - 1}>) -> &mut Self::Output {
+    fn index_mut(&mut self, index: Ranged<MIN, MAX>) -> &mut Self::Output {
         unsafe{self.get_unchecked_mut(index.usize())}
     }
 }
 
+/// Two-dimensional index `(row, column)` of `[[T; W]; H]`
+///
+/// Indexes the arrays of arrays with no bounds check, if the row range fits `0..H`
+/// and the column range fits `0..W`. A plain tuple can not be used as the index
+/// for the arrays, since both the tuple and the array are foreign types.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let mut grid = [[0; 4]; 3];
+/// let (row, col): (Ranged<0, 2>, Ranged<0, 3>) = (r!([] 1), r!([] 2));
+/// grid[Idx2(row, col)] = 5;
+/// assert_eq!(grid[1][2], 5);
+///
+/// // Convert to and from the row-major flat index
+/// let flat: Ranged<0, 11> = Idx2(row, col).flatten();
+/// assert_eq!(flat, 6);
+/// assert_eq!(Idx2::unflatten(flat), Idx2(row, col));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Idx2<R, C>(pub R, pub C);
+
+/// Three-dimensional index `(layer, row, column)` of `[[[T; W]; H]; L]`
+///
+/// Indexes the arrays with no bounds check, if every range fits the corresponding dimension.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let mut cube = [[[0; 2]; 3]; 4];
+/// cube[Idx3(r!(3), r!(2), r!(1))] = 1;
+/// assert_eq!(cube[3][2][1], 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Idx3<D, R, C>(pub D, pub R, pub C);
+
+impl<R, C> From<(R, C)> for Idx2<R, C> {
+    fn from((r, c): (R, C)) -> Self {
+        Self(r, c)
+    }
+}
+
+impl<D, R, C> From<(D, R, C)> for Idx3<D, R, C> {
+    fn from((d, r, c): (D, R, C)) -> Self {
+        Self(d, r, c)
+    }
+}
+
+impl<const RMAX: irang, const CMAX: irang> Idx2<Ranged<0, RMAX>, Ranged<0, CMAX>>
+where
+    Assert<{allow_range(memlayout(0, RMAX))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CMAX))}>: IsAllowed,
+{
+    /// Convert to the row-major index of the flattened `[T; (RMAX+1)*(CMAX+1)]`
+    #[must_use]
+    pub const fn flatten(self) -> Ranged<0, {(RMAX+1)*(CMAX+1) - 1}>
+    where
+        Assert<{allow_range(memlayout(0, (RMAX+1)*(CMAX+1) - 1))}>: IsAllowed,
+    {
+        unsafe{ Ranged::unchecked_new(self.0.get()*(CMAX+1) + self.1.get()) }
+    }
+
+    /// Convert from the row-major index of the flattened `[T; (RMAX+1)*(CMAX+1)]`
+    #[must_use]
+    pub const fn unflatten(flat: Ranged<0, {(RMAX+1)*(CMAX+1) - 1}>) -> Self
+    where
+        Assert<{allow_range(memlayout(0, (RMAX+1)*(CMAX+1) - 1))}>: IsAllowed,
+    {
+        let v = flat.get();
+        unsafe{ Self(Ranged::unchecked_new(v / (CMAX+1)), Ranged::unchecked_new(v % (CMAX+1))) }
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl<T, const H: usize, const W: usize, const R0: irang, const R1: irang, const C0: irang, const C1: irang>
+core::ops::Index<Idx2<Ranged<R0, R1>, Ranged<C0, C1>>> for [[T; W]; H]
+where
+    Assert<{allow_range(memlayout(R0, R1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(C0, C1))}>: IsAllowed,
+    // Check if the ranges fit the array dimensions
+    Assert<{conversions::converter_checkers::usize(R0, R1)}>: IsAllowed,
+    Assert<{conversions::lessthan(R1, H as i128)}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(C0, C1)}>: IsAllowed,
+    Assert<{conversions::lessthan(C1, W as i128)}>: IsAllowed,
+{
+    type Output = T;
+    fn index(&self, Idx2(r, c): Idx2<Ranged<R0, R1>, Ranged<C0, C1>>) -> &Self::Output {
+        unsafe{self.get_unchecked(r.usize()).get_unchecked(c.usize())}
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl<T, const H: usize, const W: usize, const R0: irang, const R1: irang, const C0: irang, const C1: irang>
+core::ops::IndexMut<Idx2<Ranged<R0, R1>, Ranged<C0, C1>>> for [[T; W]; H]
+where
+    Assert<{allow_range(memlayout(R0, R1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(C0, C1))}>: IsAllowed,
+    // Check if the ranges fit the array dimensions
+    Assert<{conversions::converter_checkers::usize(R0, R1)}>: IsAllowed,
+    Assert<{conversions::lessthan(R1, H as i128)}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(C0, C1)}>: IsAllowed,
+    Assert<{conversions::lessthan(C1, W as i128)}>: IsAllowed,
+{
+    fn index_mut(&mut self, Idx2(r, c): Idx2<Ranged<R0, R1>, Ranged<C0, C1>>) -> &mut Self::Output {
+        unsafe{self.get_unchecked_mut(r.usize()).get_unchecked_mut(c.usize())}
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl<T, const L: usize, const H: usize, const W: usize,
+    const D0: irang, const D1: irang, const R0: irang, const R1: irang, const C0: irang, const C1: irang>
+core::ops::Index<Idx3<Ranged<D0, D1>, Ranged<R0, R1>, Ranged<C0, C1>>> for [[[T; W]; H]; L]
+where
+    Assert<{allow_range(memlayout(D0, D1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(D0, D1)}>: IsAllowed,
+    Assert<{conversions::lessthan(D1, L as i128)}>: IsAllowed,
+    Assert<{allow_range(memlayout(R0, R1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(C0, C1))}>: IsAllowed,
+    // Check if the ranges fit the array dimensions
+    Assert<{conversions::converter_checkers::usize(R0, R1)}>: IsAllowed,
+    Assert<{conversions::lessthan(R1, H as i128)}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(C0, C1)}>: IsAllowed,
+    Assert<{conversions::lessthan(C1, W as i128)}>: IsAllowed,
+{
+    type Output = T;
+    fn index(&self, Idx3(d, r, c): Idx3<Ranged<D0, D1>, Ranged<R0, R1>, Ranged<C0, C1>>) -> &Self::Output {
+        unsafe{self.get_unchecked(d.usize()).get_unchecked(r.usize()).get_unchecked(c.usize())}
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl<T, const L: usize, const H: usize, const W: usize,
+    const D0: irang, const D1: irang, const R0: irang, const R1: irang, const C0: irang, const C1: irang>
+core::ops::IndexMut<Idx3<Ranged<D0, D1>, Ranged<R0, R1>, Ranged<C0, C1>>> for [[[T; W]; H]; L]
+where
+    Assert<{allow_range(memlayout(D0, D1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(D0, D1)}>: IsAllowed,
+    Assert<{conversions::lessthan(D1, L as i128)}>: IsAllowed,
+    Assert<{allow_range(memlayout(R0, R1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(C0, C1))}>: IsAllowed,
+    // Check if the ranges fit the array dimensions
+    Assert<{conversions::converter_checkers::usize(R0, R1)}>: IsAllowed,
+    Assert<{conversions::lessthan(R1, H as i128)}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(C0, C1)}>: IsAllowed,
+    Assert<{conversions::lessthan(C1, W as i128)}>: IsAllowed,
+{
+    fn index_mut(&mut self, Idx3(d, r, c): Idx3<Ranged<D0, D1>, Ranged<R0, R1>, Ranged<C0, C1>>) -> &mut Self::Output {
+        unsafe{self.get_unchecked_mut(d.usize()).get_unchecked_mut(r.usize()).get_unchecked_mut(c.usize())}
+    }
+}
+
 #[allow(clippy::cast_sign_loss)]
 impl<T, const N: usize, const MIN: irang, const MAX: irang>
 core::ops::Index<ConstInclusiveRange<MIN, MAX>> for [T; N] 
@@ -67,3 +234,471 @@ where
         }
     }
 }
+
+#[allow(clippy::cast_sign_loss)]
+impl<T, const N: usize, const START: irang, const END: irang>
+core::ops::Index<ConstRange<START, END>> for [T; N]
+where
+    // Constraint required by ConstRange
+    Assert<{allow_range(memlayout(START, END - 1))}>: IsAllowed,
+    // Constraint required by the output array
+    [T; (END-START) as usize]:,
+    // Check if the range fits the array length (min)
+    Assert<{conversions::converter_checkers::usize(START, END - 1)}>: IsAllowed,
+    // Check if the range fits the array length (max)
+    Assert<{conversions::lesseq(END, N as i128)}>: IsAllowed,
+{
+    type Output = [T; (END-START) as usize];
+    fn index(&self, _index: ConstRange<START, END>) -> &Self::Output {
+        unsafe{
+            &*self.get_unchecked((START as usize)..(END as usize)).as_ptr().cast()
+        }
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl<T, const N: usize, const START: irang, const END: irang>
+core::ops::IndexMut<ConstRange<START, END>> for [T; N]
+where
+    // Constraint required by ConstRange
+    Assert<{allow_range(memlayout(START, END - 1))}>: IsAllowed,
+    // Constraint required by the output array
+    [T; (END-START) as usize]:,
+    // Check if the range fits the array length (min)
+    Assert<{conversions::converter_checkers::usize(START, END - 1)}>: IsAllowed,
+    // Check if the range fits the array length (max)
+    Assert<{conversions::lesseq(END, N as i128)}>: IsAllowed,
+{
+    fn index_mut(&mut self, _index: ConstRange<START, END>) -> &mut Self::Output {
+        unsafe{
+            &mut *self.get_unchecked_mut((START as usize)..(END as usize)).as_mut_ptr().cast()
+        }
+    }
+}
+
+#[must_use]
+#[doc(hidden)]
+pub const fn divisible(n: usize, k: usize) -> OperationPossibility {
+    allow_if(k != 0 && n.is_multiple_of(k))
+}
+
+/// Create the array calling `f` for every `Ranged` index in increasing order
+///
+/// The `Ranged` counterpart of [`core::array::from_fn`]. It is a `const fn`, so
+/// the lookup tables may be computed at compile time by a `const fn` or a const
+/// closure; this needs `#![feature(const_trait_impl)]` in the user crate.
+///
+/// ```
+/// #![feature(adt_const_params, generic_const_exprs, const_trait_impl)]
+/// use ranged_integers::*;
+///
+/// const fn popcount(byte: Ranged<0, 255>) -> u8 {
+///     byte.get().count_ones() as u8
+/// }
+/// const POPCOUNT: [u8; 256] = array_from_fn(popcount);
+/// assert_eq!(POPCOUNT[r!([0 255] 7)], 3);
+///
+/// // The closure parameter type is not inferred
+/// let offsets: [i32; 4] = array_from_fn(|i: Ranged<0, 3>| i.i32() * 10);
+/// assert_eq!(offsets, [0, 10, 20, 30]);
+/// ```
+pub const fn array_from_fn<T, const N: usize, F>(f: F) -> [T; N]
+where
+    // The normalization bound, see the module header
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    F: [const] FnMut(Ranged<0, {N as irang - 1}>) -> T + [const] Destruct,
+{
+    // The keys `0..N` are the indices
+    unsafe{fill_by_keys::<T, N, 0, {N as irang - 1}, F>(f)}
+}
+
+/// Create the array calling `f` for the keys `MIN, MIN+1, ...` in increasing order
+///
+/// Shared by [`array_from_fn`] and `RangedMap::from_const_fn`. If `f` panics, the
+/// elements created so far are dropped.
+///
+/// # Safety
+///
+/// `N` must not exceed `MAX-MIN+1`, so all the keys passed to `f` are in range.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) const unsafe fn fill_by_keys<T, const N: usize, const MIN: irang, const MAX: irang, F>(mut f: F) -> [T; N]
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    F: [const] FnMut(Ranged<MIN, MAX>) -> T + [const] Destruct,
+{
+    let mut guard = FillGuard { data: [const { MaybeUninit::uninit() }; N], len: 0 };
+    while guard.len < N {
+        guard.data[guard.len].write(f(unsafe{Ranged::unchecked_new(MIN + guard.len as irang)}));
+        guard.len += 1;
+    }
+    // All the elements are initialized, the guard must not drop them
+    let data = unsafe{(&raw const guard.data).cast::<[T; N]>().read()};
+    core::mem::forget(guard);
+    data
+}
+
+// The array being filled by `fill_by_keys`; drops the initialized prefix `0..len` on unwind
+struct FillGuard<T, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Drop for FillGuard<T, N> {
+    fn drop(&mut self) {
+        let init = core::ptr::slice_from_raw_parts_mut(self.data.as_mut_ptr().cast::<T>(), self.len);
+        unsafe{core::ptr::drop_in_place(init)};
+    }
+}
+
+/// An iterator over the array elements with their `Ranged` indices
+///
+/// Created by [`ArrayExt::enumerate_ranged`].
+#[derive(Debug)]
+pub struct EnumerateRanged<'a, T, const MAX: irang>(core::iter::Enumerate<core::slice::Iter<'a, T>>)
+where Assert<{allow_range(memlayout(0, MAX))}>: IsAllowed;
+
+impl<'a, T, const MAX: irang> Iterator for EnumerateRanged<'a, T, MAX>
+where Assert<{allow_range(memlayout(0, MAX))}>: IsAllowed,
+{
+    type Item = (Ranged<0, MAX>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(i, x)| (unsafe{Ranged::unchecked_new(i as irang)}, x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T, const MAX: irang> DoubleEndedIterator for EnumerateRanged<'_, T, MAX>
+where Assert<{allow_range(memlayout(0, MAX))}>: IsAllowed,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(i, x)| (unsafe{Ranged::unchecked_new(i as irang)}, x))
+    }
+}
+
+impl<T, const MAX: irang> ExactSizeIterator for EnumerateRanged<'_, T, MAX>
+where Assert<{allow_range(memlayout(0, MAX))}>: IsAllowed,
+{}
+
+impl<T, const MAX: irang> core::iter::FusedIterator for EnumerateRanged<'_, T, MAX>
+where Assert<{allow_range(memlayout(0, MAX))}>: IsAllowed,
+{}
+
+// Not derived, since the iterator is cloned for any `T` like `slice::Iter`
+impl<T, const MAX: irang> Clone for EnumerateRanged<'_, T, MAX>
+where Assert<{allow_range(memlayout(0, MAX))}>: IsAllowed,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Extension methods for the fixed-size arrays `[T; N]` using `Ranged` indices
+pub trait ArrayExt<T, const N: usize> {
+    /// Get a fixed-length window of the array starting at a runtime offset
+    ///
+    /// The `start` type guarantees the window lies inside the array, so no bounds check is performed.
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let samples = [1, 2, 3, 4, 5, 6];
+    /// let start: Ranged<0, 3> = r!([] 2);
+    /// assert_eq!(samples.window::<3>(start), &[3, 4, 5]);
+    /// ```
+    fn window<const L: usize>(&self, start: Ranged<0, {N as irang - L as irang}>) -> &[T; L]
+    where
+        Assert<{allow_range(memlayout(0, N as irang - L as irang))}>: IsAllowed,
+        Assert<{conversions::lesseq(L as irang, N as irang)}>: IsAllowed;
+
+    /// Get a mutable fixed-length window of the array starting at a runtime offset
+    ///
+    /// The `start` type guarantees the window lies inside the array, so no bounds check is performed.
+    fn window_mut<const L: usize>(&mut self, start: Ranged<0, {N as irang - L as irang}>) -> &mut [T; L]
+    where
+        Assert<{allow_range(memlayout(0, N as irang - L as irang))}>: IsAllowed,
+        Assert<{conversions::lesseq(L as irang, N as irang)}>: IsAllowed;
+
+    /// Split the array into two at the compile-time index `K`
+    ///
+    /// The `_fixed` suffix keeps the slice `split_at` usable on arrays.
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let (head, tail) = [1, 2, 3, 4, 5].split_fixed::<2>();
+    /// assert_eq!(head, &[1, 2]);
+    /// assert_eq!(tail, &[3, 4, 5]);
+    /// ```
+    fn split_fixed<const K: usize>(&self) -> (&[T; K], &[T; N - K])
+    where
+        Assert<{conversions::lesseq(K as irang, N as irang)}>: IsAllowed,
+        [T; N - K]:;
+
+    /// Split the array into two mutable parts at the compile-time index `K`
+    fn split_fixed_mut<const K: usize>(&mut self) -> (&mut [T; K], &mut [T; N - K])
+    where
+        Assert<{conversions::lesseq(K as irang, N as irang)}>: IsAllowed,
+        [T; N - K]:;
+
+    /// Concatenate two arrays
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!([1, 2].concat_fixed([3, 4, 5]), [1, 2, 3, 4, 5]);
+    /// ```
+    fn concat_fixed<const B: usize>(self, other: [T; B]) -> [T; N + B]
+    where
+        [T; N + B]:;
+
+    /// View the array as `N/K` chunks of length `K`, where `K` divides `N`
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let chunks = [1, 2, 3, 4, 5, 6].chunks_fixed::<2>();
+    /// assert_eq!(chunks, &[[1, 2], [3, 4], [5, 6]]);
+    /// assert_eq!(chunks[r!(1)], [3, 4]);
+    /// ```
+    fn chunks_fixed<const K: usize>(&self) -> &[[T; K]; N / K]
+    where
+        Assert<{divisible(N, K)}>: IsAllowed,
+        [[T; K]; N / K]:;
+
+    /// View the array as `N/K` mutable chunks of length `K`, where `K` divides `N`
+    fn chunks_fixed_mut<const K: usize>(&mut self) -> &mut [[T; K]; N / K]
+    where
+        Assert<{divisible(N, K)}>: IsAllowed,
+        [[T; K]; N / K]:;
+
+    /// Get the chunk of length `K` at the runtime chunk index `i`, where `K` divides `N`
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let regs = [1, 2, 3, 4, 5, 6];
+    /// let i: Ranged<0, 1> = r!([] 1);
+    /// assert_eq!(regs.chunk::<3>(i), &[4, 5, 6]);
+    /// ```
+    fn chunk<const K: usize>(&self, i: Ranged<0, {(N / K) as irang - 1}>) -> &[T; K]
+    where
+        Assert<{divisible(N, K)}>: IsAllowed,
+        Assert<{allow_range(memlayout(0, (N / K) as irang - 1))}>: IsAllowed;
+
+    /// Get the mutable chunk of length `K` at the runtime chunk index `i`, where `K` divides `N`
+    fn chunk_mut<const K: usize>(&mut self, i: Ranged<0, {(N / K) as irang - 1}>) -> &mut [T; K]
+    where
+        Assert<{divisible(N, K)}>: IsAllowed,
+        Assert<{allow_range(memlayout(0, (N / K) as irang - 1))}>: IsAllowed;
+
+    /// Iterate over the elements together with their `Ranged` indices
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let arr = [10, 20, 30];
+    /// for (i, x) in arr.enumerate_ranged() {
+    ///     assert_eq!(arr[i], *x);  // No bounds check
+    /// }
+    /// ```
+    fn enumerate_ranged(&self) -> EnumerateRanged<'_, T, {N as irang - 1}>
+    where
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed;
+
+    /// Search for the first element satisfying the predicate and return its index
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let arr = [1, 4, 9, 16];
+    /// let i = arr.position_ranged(|&x| x > 5).unwrap();
+    /// assert_eq!(arr[i], 9);
+    /// ```
+    fn position_ranged(&self, pred: impl FnMut(&T) -> bool) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed;
+
+    /// Search for the last element satisfying the predicate and return its index
+    fn rposition_ranged(&self, pred: impl FnMut(&T) -> bool) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed;
+
+    /// Return the index of the first element equal to `value`
+    fn find_index(&self, value: &T) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        T: PartialEq,
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed;
+
+    /// Return the index of the maximum element
+    ///
+    /// If several elements are equally maximum, the last index is returned, as [`Iterator::max`] does.
+    /// The result is `Some` unless the array is empty, which does not pass the type check.
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let arr = [3, 7, 2, 7];
+    /// assert_eq!(arr.max_index().unwrap(), 3);
+    /// assert_eq!(arr.min_index().unwrap(), 2);
+    /// ```
+    fn max_index(&self) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        T: Ord,
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed;
+
+    /// Return the index of the minimum element
+    ///
+    /// If several elements are equally minimum, the first index is returned, as [`Iterator::min`] does.
+    fn min_index(&self) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        T: Ord,
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed;
+}
+
+#[allow(clippy::cast_sign_loss)]
+#[allow(clippy::cast_possible_truncation)]
+impl<T, const N: usize> ArrayExt<T, N> for [T; N]
+where
+    // The normalization bound, see the module header
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+{
+    fn window<const L: usize>(&self, start: Ranged<0, {N as irang - L as irang}>) -> &[T; L]
+    where
+        Assert<{allow_range(memlayout(0, N as irang - L as irang))}>: IsAllowed,
+        Assert<{conversions::lesseq(L as irang, N as irang)}>: IsAllowed,
+    {
+        unsafe{
+            &*self.as_ptr().add(start.get() as usize).cast()
+        }
+    }
+
+    fn window_mut<const L: usize>(&mut self, start: Ranged<0, {N as irang - L as irang}>) -> &mut [T; L]
+    where
+        Assert<{allow_range(memlayout(0, N as irang - L as irang))}>: IsAllowed,
+        Assert<{conversions::lesseq(L as irang, N as irang)}>: IsAllowed,
+    {
+        unsafe{
+            &mut *self.as_mut_ptr().add(start.get() as usize).cast()
+        }
+    }
+
+    fn split_fixed<const K: usize>(&self) -> (&[T; K], &[T; N - K])
+    where
+        Assert<{conversions::lesseq(K as irang, N as irang)}>: IsAllowed,
+        [T; N - K]:,
+    {
+        unsafe{
+            let p = self.as_ptr();
+            (&*p.cast(), &*p.add(K).cast())
+        }
+    }
+
+    fn split_fixed_mut<const K: usize>(&mut self) -> (&mut [T; K], &mut [T; N - K])
+    where
+        Assert<{conversions::lesseq(K as irang, N as irang)}>: IsAllowed,
+        [T; N - K]:,
+    {
+        unsafe{
+            let p = self.as_mut_ptr();
+            (&mut *p.cast(), &mut *p.add(K).cast())
+        }
+    }
+
+    fn concat_fixed<const B: usize>(self, other: [T; B]) -> [T; N + B]
+    where
+        [T; N + B]:,
+    {
+        // The elements are moved to the output, so the sources must not be dropped
+        let a = ManuallyDrop::new(self);
+        let b = ManuallyDrop::new(other);
+        let mut out = MaybeUninit::<[T; N + B]>::uninit();
+        unsafe{
+            let p = out.as_mut_ptr().cast::<T>();
+            core::ptr::copy_nonoverlapping(a.as_ptr(), p, N);
+            core::ptr::copy_nonoverlapping(b.as_ptr(), p.add(N), B);
+            out.assume_init()
+        }
+    }
+
+    fn chunks_fixed<const K: usize>(&self) -> &[[T; K]; N / K]
+    where
+        Assert<{divisible(N, K)}>: IsAllowed,
+        [[T; K]; N / K]:,
+    {
+        unsafe{
+            &*self.as_ptr().cast()
+        }
+    }
+
+    fn chunks_fixed_mut<const K: usize>(&mut self) -> &mut [[T; K]; N / K]
+    where
+        Assert<{divisible(N, K)}>: IsAllowed,
+        [[T; K]; N / K]:,
+    {
+        unsafe{
+            &mut *self.as_mut_ptr().cast()
+        }
+    }
+
+    fn chunk<const K: usize>(&self, i: Ranged<0, {(N / K) as irang - 1}>) -> &[T; K]
+    where
+        Assert<{divisible(N, K)}>: IsAllowed,
+        Assert<{allow_range(memlayout(0, (N / K) as irang - 1))}>: IsAllowed,
+    {
+        unsafe{
+            &*self.as_ptr().add(i.get() as usize * K).cast()
+        }
+    }
+
+    fn chunk_mut<const K: usize>(&mut self, i: Ranged<0, {(N / K) as irang - 1}>) -> &mut [T; K]
+    where
+        Assert<{divisible(N, K)}>: IsAllowed,
+        Assert<{allow_range(memlayout(0, (N / K) as irang - 1))}>: IsAllowed,
+    {
+        unsafe{
+            &mut *self.as_mut_ptr().add(i.get() as usize * K).cast()
+        }
+    }
+
+    fn enumerate_ranged(&self) -> EnumerateRanged<'_, T, {N as irang - 1}>
+    where
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    {
+        EnumerateRanged(self.iter().enumerate())
+    }
+
+    fn position_ranged(&self, pred: impl FnMut(&T) -> bool) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    {
+        self.iter().position(pred).map(|i| unsafe{Ranged::unchecked_new(i as irang)})
+    }
+
+    fn rposition_ranged(&self, pred: impl FnMut(&T) -> bool) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    {
+        self.iter().rposition(pred).map(|i| unsafe{Ranged::unchecked_new(i as irang)})
+    }
+
+    fn find_index(&self, value: &T) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        T: PartialEq,
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    {
+        self.position_ranged(|x| x == value)
+    }
+
+    fn max_index(&self) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        T: Ord,
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    {
+        self.enumerate_ranged().max_by_key(|&(_, x)| x).map(|(i, _)| i)
+    }
+
+    fn min_index(&self) -> Option<Ranged<0, {N as irang - 1}>>
+    where
+        T: Ord,
+        Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    {
+        self.enumerate_ranged().min_by_key(|&(_, x)| x).map(|(i, _)| i)
+    }
+}
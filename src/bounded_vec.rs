@@ -0,0 +1,319 @@
+// A vector stored inline in `[MaybeUninit<T>; CAP]` with the length kept as `Ranged<0, CAP>`.
+// The elements `0..len` are initialized, the rest are not.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+use core::mem::MaybeUninit;
+use crate::{allow_range, conversions, irang, memlayout, Assert, IsAllowed, Ranged};
+
+/// A stack-allocated vector with the capacity `CAP` and the `Ranged` length
+///
+/// The length is `Ranged<0, CAP>`, so it carries the capacity bound to the
+/// code using it. The vector is indexed with `Ranged` indices fitting `0..CAP`,
+/// which need only the check against the current length: the index type can't
+/// depend on the length known at runtime. The full vector is viewed as `[T; CAP]`
+/// with [`as_array`](Self::as_array), which is indexed with no check at all.
+/// The capacity is at least 1.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let mut v = BoundedVec::<i32, 4>::new();
+/// assert_eq!(v.push(10).unwrap(), 1);
+/// assert_eq!(v.push(20).unwrap(), 2);
+///
+/// let len: Ranged<0, 4> = v.len();
+/// assert_eq!(len, 2);
+/// assert_eq!(v[r!([0 3] 1)], 20);
+///
+/// let (x, len) = v.pop().unwrap();
+/// assert_eq!((x, len.get()), (20, 1));
+/// ```
+pub struct BoundedVec<T, const CAP: usize>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    // The index type of the full vector array; also needed to normalize the length type,
+    // see the normalization note in the header of arrays.rs
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    data: [MaybeUninit<T>; CAP],
+    len: Ranged<0, {CAP as irang}>,
+}
+
+impl<T, const CAP: usize> BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    /// Create an empty vector
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            data: [const { MaybeUninit::uninit() }; CAP],
+            len: unsafe{Ranged::unchecked_new(0)},
+        }
+    }
+
+    /// The number of elements in the vector
+    #[must_use]
+    pub const fn len(&self) -> Ranged<0, {CAP as irang}> {
+        self.len
+    }
+
+    /// The maximum number of elements in the vector
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    /// Check if the vector has no elements
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len.get() == 0
+    }
+
+    /// Check if the vector has `CAP` elements
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len.get() == CAP as irang
+    }
+
+    /// Append an element and return the new length, or give the element back if the vector is full
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the vector is full.
+    pub const fn push(&mut self, value: T) -> Result<Ranged<0, {CAP as irang}>, T> {
+        let len = self.len.get() as usize;
+        if len == CAP {
+            return Err(value);
+        }
+        unsafe{
+            self.data.get_unchecked_mut(len).write(value);
+            self.len = Ranged::unchecked_new(len as irang + 1);
+        }
+        Ok(self.len)
+    }
+
+    /// Remove the last element and return it with the new length, or `None` if the vector is empty
+    pub const fn pop(&mut self) -> Option<(T, Ranged<0, {CAP as irang}>)> {
+        let len = self.len.get() as usize;
+        if len == 0 {
+            return None;
+        }
+        unsafe{
+            self.len = Ranged::unchecked_new(len as irang - 1);
+            Some((self.data.get_unchecked(len - 1).assume_init_read(), self.len))
+        }
+    }
+
+    /// Shorten the vector to `len` elements, dropping the rest
+    ///
+    /// Does nothing if `len` is not less than the current length.
+    pub fn truncate(&mut self, len: Ranged<0, {CAP as irang}>) {
+        let old = self.len.get() as usize;
+        let new = len.get() as usize;
+        if new >= old {
+            return;
+        }
+        // Set the length first, so a panicking drop does not cause a double drop
+        self.len = len;
+        unsafe{
+            let tail = core::ptr::slice_from_raw_parts_mut(self.data.as_mut_ptr().add(new).cast::<T>(), old - new);
+            core::ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Remove all the elements
+    pub fn clear(&mut self) {
+        self.truncate(unsafe{Ranged::unchecked_new(0)});
+    }
+
+    /// Get a reference to the element, or `None` if the index is not less than the length
+    #[must_use]
+    pub const fn get<const MIN: irang, const MAX: irang>(&self, index: Ranged<MIN, MAX>) -> Option<&T>
+    where
+        Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+        Assert<{conversions::converter_checkers::usize(MIN, MAX)}>: IsAllowed,
+        Assert<{conversions::lessthan(MAX, CAP as irang)}>: IsAllowed,
+    {
+        if index.get() < self.len.get() {
+            Some(unsafe{self.data.get_unchecked(index.usize()).assume_init_ref()})
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the element, or `None` if the index is not less than the length
+    #[must_use]
+    pub const fn get_mut<const MIN: irang, const MAX: irang>(&mut self, index: Ranged<MIN, MAX>) -> Option<&mut T>
+    where
+        Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+        Assert<{conversions::converter_checkers::usize(MIN, MAX)}>: IsAllowed,
+        Assert<{conversions::lessthan(MAX, CAP as irang)}>: IsAllowed,
+    {
+        if index.get() < self.len.get() {
+            Some(unsafe{self.data.get_unchecked_mut(index.usize()).assume_init_mut()})
+        } else {
+            None
+        }
+    }
+
+    /// View the elements as a slice
+    #[must_use]
+    pub const fn as_slice(&self) -> &[T] {
+        unsafe{core::slice::from_raw_parts(self.data.as_ptr().cast(), self.len.get() as usize)}
+    }
+
+    /// View the elements as a mutable slice
+    #[must_use]
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe{core::slice::from_raw_parts_mut(self.data.as_mut_ptr().cast(), self.len.get() as usize)}
+    }
+
+    /// View the full vector as an array, or `None` if the vector is not full
+    ///
+    /// The array is indexed with `Ranged<0, {CAP-1}>` with no bounds check.
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let mut v = BoundedVec::<char, 3>::new();
+    /// v.push('a').unwrap();
+    /// assert!(v.as_array().is_none());
+    ///
+    /// v.push('b').unwrap();
+    /// v.push('c').unwrap();
+    /// let arr = v.as_array().unwrap();
+    /// assert_eq!(arr[r!([0 2] 2)], 'c');
+    /// ```
+    #[must_use]
+    pub const fn as_array(&self) -> Option<&[T; CAP]> {
+        if self.is_full() {
+            Some(unsafe{&*self.data.as_ptr().cast::<[T; CAP]>()})
+        } else {
+            None
+        }
+    }
+
+    /// View the full vector as a mutable array, or `None` if the vector is not full
+    #[must_use]
+    pub const fn as_mut_array(&mut self) -> Option<&mut [T; CAP]> {
+        if self.is_full() {
+            Some(unsafe{&mut *self.data.as_mut_ptr().cast::<[T; CAP]>()})
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, const CAP: usize> Drop for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    fn drop(&mut self) {
+        unsafe{core::ptr::drop_in_place(self.as_mut_slice())}
+    }
+}
+
+impl<T, const CAP: usize> Default for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const CAP: usize> Clone for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    fn clone(&self) -> Self {
+        let mut out = Self::new();
+        for x in self.as_slice() {
+            // The same capacity, never full before all the elements are cloned
+            let _ = out.push(x.clone());
+        }
+        out
+    }
+}
+
+impl<T: core::fmt::Debug, const CAP: usize> core::fmt::Debug for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T: PartialEq, const CAP: usize> PartialEq for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, const CAP: usize> Eq for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{}
+
+impl<T, const CAP: usize> core::ops::Deref for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const CAP: usize> core::ops::DerefMut for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+{
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const CAP: usize, const MIN: irang, const MAX: irang> core::ops::Index<Ranged<MIN, MAX>> for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    // The index type fits the capacity, so only the length is checked
+    Assert<{conversions::converter_checkers::usize(MIN, MAX)}>: IsAllowed,
+    Assert<{conversions::lessthan(MAX, CAP as irang)}>: IsAllowed,
+{
+    type Output = T;
+    fn index(&self, index: Ranged<MIN, MAX>) -> &T {
+        let len = self.len.get();
+        self.get(index).unwrap_or_else(|| panic!("index out of bounds: the len is {len} but the index is {index}"))
+    }
+}
+
+impl<T, const CAP: usize, const MIN: irang, const MAX: irang> core::ops::IndexMut<Ranged<MIN, MAX>> for BoundedVec<T, CAP>
+where
+    Assert<{allow_range(memlayout(0, CAP as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, CAP as irang - 1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(MIN, MAX)}>: IsAllowed,
+    Assert<{conversions::lessthan(MAX, CAP as irang)}>: IsAllowed,
+{
+    fn index_mut(&mut self, index: Ranged<MIN, MAX>) -> &mut T {
+        let len = self.len.get();
+        self.get_mut(index).unwrap_or_else(|| panic!("index out of bounds: the len is {len} but the index is {index}"))
+    }
+}
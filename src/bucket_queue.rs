@@ -0,0 +1,200 @@
+// A bucket queue: a FIFO bucket per priority value and a bitset of the non-empty buckets.
+// The buckets are a boxed slice indexed by `priority-MIN`, so the wide priority ranges
+// do not need the space on the stack. The lowest and the highest non-empty priorities are
+// cached; when a pop empties the cached bucket, the bitset is scanned from it to the next
+// non-empty one, so the scans of a monotone workload cover the range once in total.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use crate::{allow_range, conversions, irang, memlayout, Assert, IsAllowed, Ranged, RangedSet};
+use crate::ranged_set::set_words;
+
+/// A priority queue with `Ranged<MIN, MAX>` priorities
+///
+/// Pushing takes constant amortized time, peeking takes constant time. The lowest and the highest
+/// non-empty priorities are cached, and popping moves the cache to the next non-empty
+/// bucket. Popping takes constant amortized time for the monotone workloads, where
+/// the pushed priorities are not below the last popped minimum (for `pop_min`),
+/// like in Dijkstra's algorithm, or not above the last popped maximum (for `pop_max`).
+/// A single pop takes `O((MAX-MIN)/64)` time in the worst case.
+///
+/// The items of equal priority are popped in the order they were pushed.
+/// Available with the `alloc` feature.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// # #[cfg(feature = "alloc")] fn main() {
+/// let mut tasks = BucketQueue::<0, 3, &str>::new();
+/// tasks.push(r!(2), "write");
+/// tasks.push(r!(0), "idle");
+/// tasks.push(r!(3), "interrupt");
+///
+/// let (priority, task) = tasks.pop_max().unwrap();
+/// assert_eq!((priority.get(), task), (3, "interrupt"));
+/// assert_eq!(tasks.pop_min().unwrap().1, "idle");
+/// assert_eq!(tasks.len(), 1);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BucketQueue<const MIN: irang, const MAX: irang, T>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    buckets: Box<[VecDeque<T>]>,
+    occupied: RangedSet<MIN, MAX>,
+    len: usize,
+    // The lowest and the highest non-empty priorities, meaningful if `len > 0`
+    min: irang,
+    max: irang,
+}
+
+impl<const MIN: irang, const MAX: irang, T> BucketQueue<MIN, MAX, T>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    /// Create an empty queue
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            buckets: (MIN..=MAX).map(|_| VecDeque::new()).collect(),
+            occupied: RangedSet::new(),
+            len: 0,
+            min: MIN,
+            max: MAX,
+        }
+    }
+
+    // The bucket of the priority
+    const fn bucket(priority: irang) -> usize {
+        (priority - MIN) as usize
+    }
+
+    /// The number of items in the queue
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the queue has no items
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add an item with the given priority
+    ///
+    /// Any priority range inside `MIN..=MAX` is accepted.
+    pub fn push<const PMIN: irang, const PMAX: irang>(&mut self, priority: Ranged<PMIN, PMAX>, item: T)
+    where
+        Assert<{allow_range(memlayout(PMIN, PMAX))}>: IsAllowed,
+        Assert<{conversions::lesseq(MIN, PMIN)}>: IsAllowed,
+        Assert<{conversions::lesseq(PMAX, MAX)}>: IsAllowed,
+    {
+        let p = priority.get();
+        self.buckets[Self::bucket(p)].push_back(item);
+        self.occupied.insert_value(p);
+        if self.len == 0 {
+            (self.min, self.max) = (p, p);
+        } else {
+            self.min = self.min.min(p);
+            self.max = self.max.max(p);
+        }
+        self.len += 1;
+    }
+
+    // Take the oldest item of the non-empty bucket, returning whether the bucket became empty
+    fn take(&mut self, priority: irang) -> (Ranged<MIN, MAX>, T, bool) {
+        let bucket = &mut self.buckets[Self::bucket(priority)];
+        let item = bucket.pop_front().expect("the cached bucket is not empty");
+        let emptied = bucket.is_empty();
+        if emptied {
+            self.occupied.remove_value(priority);
+        }
+        self.len -= 1;
+        (unsafe{Ranged::unchecked_new(priority)}, item, emptied)
+    }
+
+    /// Remove the oldest item of the lowest priority, return it with the priority
+    pub fn pop_min(&mut self) -> Option<(Ranged<MIN, MAX>, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        let (priority, item, emptied) = self.take(self.min);
+        if emptied {
+            // The buckets below the emptied one are empty; none is left if the queue is empty
+            if let Some(p) = self.occupied.first_from(self.min) {
+                self.min = p;
+            }
+        }
+        Some((priority, item))
+    }
+
+    /// Remove the oldest item of the highest priority, return it with the priority
+    pub fn pop_max(&mut self) -> Option<(Ranged<MIN, MAX>, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        let (priority, item, emptied) = self.take(self.max);
+        if emptied {
+            // The buckets above the emptied one are empty; none is left if the queue is empty
+            if let Some(p) = self.occupied.last_from(self.max) {
+                self.max = p;
+            }
+        }
+        Some((priority, item))
+    }
+
+    /// Get the item to be returned by [`pop_min`](Self::pop_min) without removing it
+    #[must_use]
+    pub fn peek_min(&self) -> Option<(Ranged<MIN, MAX>, &T)> {
+        if self.len == 0 {
+            return None;
+        }
+        self.buckets[Self::bucket(self.min)].front().map(|item| (unsafe{Ranged::unchecked_new(self.min)}, item))
+    }
+
+    /// Get the item to be returned by [`pop_max`](Self::pop_max) without removing it
+    #[must_use]
+    pub fn peek_max(&self) -> Option<(Ranged<MIN, MAX>, &T)> {
+        if self.len == 0 {
+            return None;
+        }
+        self.buckets[Self::bucket(self.max)].front().map(|item| (unsafe{Ranged::unchecked_new(self.max)}, item))
+    }
+
+    /// Remove all the items
+    pub fn clear(&mut self) {
+        for priority in &self.occupied {
+            self.buckets[Self::bucket(priority.get())].clear();
+        }
+        self.occupied.clear();
+        self.len = 0;
+    }
+}
+
+impl<const MIN: irang, const MAX: irang, T> Default for BucketQueue<MIN, MAX, T>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const MIN: irang, const MAX: irang, T: core::fmt::Debug> core::fmt::Debug for BucketQueue<MIN, MAX, T>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Only the non-empty buckets
+        f.debug_map().entries(self.occupied.iter().map(|p| (p.get(), &self.buckets[Self::bucket(p.get())]))).finish()
+    }
+}
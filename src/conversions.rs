@@ -5,7 +5,13 @@ use crate::value_check::allow_if;
 use crate::{Assert, IsAllowed, OperationPossibility, Ranged, irang, memlayout, arithmetics::allow_division};
 /// Convert an integer value to Ranged according to its own bounds.
 ///
-/// Implemented for integer primitives.
+/// Implemented for integer primitives except for `i128` and `u128`, `bool` (`Ranged<0, 1>`),
+/// `char` (`Ranged<0, 0x10FFFF>`) and the nonzero integers. The unsigned nonzero types
+/// convert to `Ranged<1, MAX>`; the signed ones can't exclude zero from a single range,
+/// so they convert to the bounds of the primitive.
+///
+/// The reverse conversion is `From` if the bounds guarantee the validity, and `TryFrom`
+/// for the exact output type of `as_ranged`.
 ///
 /// ```
 /// # #![feature(adt_const_params, generic_const_exprs)]
@@ -80,6 +86,405 @@ macro_rules! as_ranged_impl {
 
 int_ranged_converters! {i8 u8 i16 u16 i32 u32 i64 u64 i128 isize usize}
 as_ranged_impl! {i8 u8 i16 u16 i32 u32 i64 u64 isize usize}
+// i128 and u128 are not implemented, since their bounds exceed the widest Ranged layout
+
+#[must_use]
+#[doc(hidden)]
+pub const fn allow_bool(min: irang, max: irang) -> OperationPossibility {
+    allow_if(min >= 0 && max <= 1)
+}
+
+// The range must not cross the surrogate code points 0xD800..=0xDFFF
+#[must_use]
+#[doc(hidden)]
+pub const fn allow_char(min: irang, max: irang) -> OperationPossibility {
+    allow_if(min >= 0 && max <= 0x0010_FFFF && (max < 0xD800 || min > 0xDFFF))
+}
+
+impl AsRanged for bool {
+    type Res = Ranged<0, 1>;
+    fn as_ranged(self) -> Self::Res {
+        unsafe {Self::Res::unchecked_new(irang::from(self))}
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> From<Ranged<MIN, MAX>> for bool
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    Assert<{allow_bool(MIN, MAX)}>: IsAllowed,
+{
+    fn from(a: Ranged<MIN, MAX>) -> Self { a.get() != 0 }
+}
+
+impl AsRanged for char {
+    type Res = Ranged<0, 0x0010_FFFF>;
+    fn as_ranged(self) -> Self::Res {
+        unsafe {Self::Res::unchecked_new(irang::from(u32::from(self)))}
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> From<Ranged<MIN, MAX>> for char
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    Assert<{allow_char(MIN, MAX)}>: IsAllowed,
+{
+    fn from(a: Ranged<MIN, MAX>) -> Self {
+        #![allow(clippy::cast_possible_truncation)]
+        #![allow(clippy::cast_sign_loss)]
+        unsafe { Self::from_u32_unchecked(a.get() as u32) }
+    }
+}
+
+// The bounds of `char::as_ranged` include the surrogates, so the reverse conversion may fail
+impl TryFrom<Ranged<0, 0x0010_FFFF>> for char {
+    type Error = core::char::CharTryFromError;
+    fn try_from(a: Ranged<0, 0x0010_FFFF>) -> Result<Self, Self::Error> {
+        Self::try_from(a.u32())
+    }
+}
+
+macro_rules! nonzero_unsigned_converters {
+    ($($nz: ident $t: ident)+) => {
+        $(
+            impl AsRanged for core::num::$nz {
+                type Res = Ranged<1, {$t::MAX as irang}>;
+                fn as_ranged(self) -> Self::Res {
+                    #![allow(clippy::cast_lossless)]
+                    unsafe {Self::Res::unchecked_new(self.get() as irang)}
+                }
+            }
+
+            impl<const MIN: irang, const MAX: irang> From<Ranged<MIN, MAX>> for core::num::$nz
+            where
+                Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+                Assert<{converter_checkers::$t(MIN, MAX)}>: IsAllowed,
+                Assert<{lesseq(1, MIN)}>: IsAllowed,
+            {
+                fn from(a: Ranged<MIN, MAX>) -> Self {
+                    unsafe { Self::new_unchecked(a.$t()) }
+                }
+            }
+        )+
+    };
+}
+
+// The signed nonzero types cover two separate ranges, so their `as_ranged` includes zero
+macro_rules! nonzero_signed_converters {
+    ($($nz: ident $t: ident)+) => {
+        $(
+            impl AsRanged for core::num::$nz {
+                type Res = Ranged<{$t::MIN as irang}, {$t::MAX as irang}>;
+                fn as_ranged(self) -> Self::Res {
+                    #![allow(clippy::cast_lossless)]
+                    unsafe {Self::Res::unchecked_new(self.get() as irang)}
+                }
+            }
+
+            impl<const MIN: irang, const MAX: irang> From<Ranged<MIN, MAX>> for core::num::$nz
+            where
+                Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+                Assert<{converter_checkers::$t(MIN, MAX)}>: IsAllowed,
+                // Zero is out of range
+                Assert<{allow_division(MIN, MAX)}>: IsAllowed,
+            {
+                fn from(a: Ranged<MIN, MAX>) -> Self {
+                    unsafe { Self::new_unchecked(a.$t()) }
+                }
+            }
+
+            impl TryFrom<Ranged<{$t::MIN as irang}, {$t::MAX as irang}>> for core::num::$nz {
+                type Error = core::num::TryFromIntError;
+                fn try_from(a: Ranged<{$t::MIN as irang}, {$t::MAX as irang}>) -> Result<Self, Self::Error> {
+                    Self::try_from(a.$t())
+                }
+            }
+        )+
+    };
+}
+
+nonzero_unsigned_converters! {NonZeroU8 u8 NonZeroU16 u16 NonZeroU32 u32 NonZeroU64 u64 NonZeroUsize usize}
+nonzero_signed_converters! {NonZeroI8 i8 NonZeroI16 i16 NonZeroI32 i32 NonZeroI64 i64 NonZeroIsize isize}
+
+impl Ranged<0, 9> {
+    /// Convert an ASCII digit `b'0'..=b'9'` to its value
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!(Ranged::<0, 9>::from_ascii_digit(b'7'), Some(r!([] 7)));
+    /// assert_eq!(Ranged::<0, 9>::from_ascii_digit(b'a'), None);
+    /// ```
+    #[must_use]
+    pub const fn from_ascii_digit(b: u8) -> Option<Self> {
+        if b.is_ascii_digit() {
+            Some(unsafe { Self::unchecked_new((b - b'0') as irang) })
+        } else {
+            None
+        }
+    }
+
+    /// Convert to an ASCII digit `b'0'..=b'9'`
+    #[must_use]
+    pub const fn to_ascii_digit(self) -> u8 {
+        b'0' + self.u8()
+    }
+}
+
+impl Ranged<0, 35> {
+    /// Convert a digit character of the given radix to its value
+    ///
+    /// Follows the rules of [`char::to_digit`]: the letters of both cases are accepted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is smaller than 2 or greater than 36, like [`char::to_digit`].
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!(Ranged::<0, 35>::from_digit_char('F', 16), Some(r!([] 15)));
+    /// assert_eq!(Ranged::<0, 35>::from_digit_char('8', 8), None);
+    /// ```
+    #[must_use]
+    pub const fn from_digit_char(c: char, radix: u32) -> Option<Self> {
+        match c.to_digit(radix) {
+            Some(d) => Some(unsafe { Self::unchecked_new(d as irang) }),
+            None => None,
+        }
+    }
+
+    /// Convert to a digit character `'0'..='9'` or `'a'..='z'`
+    ///
+    /// The result is the code point, convertible to `char` with `From`.
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!(char::from(r!([0 35] 11).to_digit_char()), 'b');
+    /// ```
+    #[must_use]
+    pub const fn to_digit_char(self) -> Ranged<{'0' as irang}, {'z' as irang}> {
+        let v = self.get();
+        let c = if v < 10 { '0' as irang + v } else { 'a' as irang + v - 10 };
+        unsafe { Ranged::unchecked_new(c) }
+    }
+}
+
+// The integers up to 2^24 (2^53) by absolute value are exactly representable in f32 (f64)
+
+#[must_use]
+#[doc(hidden)]
+pub const fn allow_f32(min: irang, max: irang) -> OperationPossibility {
+    allow_if(-(1 << f32::MANTISSA_DIGITS) <= min && max <= 1 << f32::MANTISSA_DIGITS)
+}
+
+#[must_use]
+#[doc(hidden)]
+pub const fn allow_f64(min: irang, max: irang) -> OperationPossibility {
+    allow_if(-(1 << f64::MANTISSA_DIGITS) <= min && max <= 1 << f64::MANTISSA_DIGITS)
+}
+
+impl<const MIN: irang, const MAX: irang> From<Ranged<MIN, MAX>> for f32
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    Assert<{allow_f32(MIN, MAX)}>: IsAllowed,
+{
+    fn from(a: Ranged<MIN, MAX>) -> Self {
+        #![allow(clippy::cast_precision_loss)]
+        a.get() as Self
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> From<Ranged<MIN, MAX>> for f64
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    Assert<{allow_f64(MIN, MAX)}>: IsAllowed,
+{
+    fn from(a: Ranged<MIN, MAX>) -> Self {
+        #![allow(clippy::cast_precision_loss)]
+        a.get() as Self
+    }
+}
+
+/// The rounding of a float converted to [`Ranged`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to the nearest integer, half-way cases away from zero
+    Nearest,
+    /// Round towards negative infinity
+    Floor,
+    /// Round towards positive infinity
+    Ceil,
+    /// Round towards zero
+    Trunc,
+}
+
+// Round a float to an integer. NaN is converted to 0 and the values beyond i128 are saturated.
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_precision_loss)]
+const fn round_f64(x: f64, mode: RoundingMode) -> irang {
+    // The cast truncates towards zero. The fraction is exact, because the floats
+    // large enough to lose it have no fractional part.
+    let t = x as irang;
+    let frac = x - t as f64;
+    match mode {
+        RoundingMode::Nearest if frac >= 0.5 => t.saturating_add(1),
+        RoundingMode::Nearest if frac <= -0.5 => t.saturating_sub(1),
+        RoundingMode::Floor if frac < 0.0 => t.saturating_sub(1),
+        RoundingMode::Ceil if frac > 0.0 => t.saturating_add(1),
+        _ => t,
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> Ranged<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    /// Convert to any type implementing `From<Ranged>`, usually a primitive whose range fits
+    #[must_use]
+    pub fn to<T: From<Self>>(self) -> T {
+        T::from(self)
+    }
+
+    /// Convert to an integer primitive, failing if the value does not fit
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `T::try_from(i128)` if the value is out of the `T` range.
+    pub fn try_to<T: TryFrom<irang>>(self) -> Result<T, T::Error> {
+        T::try_from(self.get())
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> Ranged<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    /// Round a float to `Ranged`, returning `None` if it is NaN or out of range after rounding
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!(Ranged::<0, 100>::from_f64_round(42.5, RoundingMode::Nearest), Some(r!([] 43)));
+    /// assert_eq!(Ranged::<0, 100>::from_f64_round(42.5, RoundingMode::Floor), Some(r!([] 42)));
+    /// assert_eq!(Ranged::<0, 100>::from_f64_round(100.2, RoundingMode::Ceil), None);
+    /// ```
+    #[must_use]
+    pub const fn from_f64_round(x: f64, mode: RoundingMode) -> Option<Self> {
+        if x.is_nan() {
+            return None;
+        }
+        Self::new(round_f64(x, mode))
+    }
+
+    /// Round a float to `Ranged`, clamping the result to the bounds
+    ///
+    /// NaN is treated as zero, like the `as` cast does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!(Ranged::<0, 100>::from_f64_saturating(42.5, RoundingMode::Trunc), 42);
+    /// assert_eq!(Ranged::<0, 100>::from_f64_saturating(-1e300, RoundingMode::Nearest), 0);
+    /// assert_eq!(Ranged::<0, 100>::from_f64_saturating(f64::INFINITY, RoundingMode::Nearest), 100);
+    /// ```
+    #[must_use]
+    pub const fn from_f64_saturating(x: f64, mode: RoundingMode) -> Self {
+        let v = round_f64(x, mode);
+        let v = if v < MIN {MIN} else if v > MAX {MAX} else {v};
+        unsafe { Self::unchecked_new(v) }
+    }
+}
+
+/// The side of the bounds violated by an out-of-range value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutOfRangeKind {
+    /// The value is less than `MIN`
+    BelowMin,
+    /// The value is greater than `MAX`
+    AboveMax,
+}
+
+/// The error of a fallible conversion of an integer into [`Ranged`]
+///
+/// Carries the rejected value and the bounds of the target type.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let err = Ranged::<1, 100>::try_from(1000_u16).unwrap_err();
+/// assert_eq!(err.value(), 1000);
+/// assert_eq!(err.kind(), OutOfRangeKind::AboveMax);
+/// assert_eq!(err.to_string(), "value 1000 is above the maximum 100 of the range 1..=100");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OutOfRangeError {
+    value: irang,
+    min: irang,
+    max: irang,
+}
+
+impl OutOfRangeError {
+    /// The value rejected by the conversion
+    #[must_use]
+    pub const fn value(&self) -> irang { self.value }
+
+    /// The lower bound of the target type
+    #[must_use]
+    pub const fn min(&self) -> irang { self.min }
+
+    /// The upper bound of the target type
+    #[must_use]
+    pub const fn max(&self) -> irang { self.max }
+
+    /// Whether the value was too low or too high
+    #[must_use]
+    pub const fn kind(&self) -> OutOfRangeKind {
+        if self.value < self.min { OutOfRangeKind::BelowMin } else { OutOfRangeKind::AboveMax }
+    }
+}
+
+impl core::fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Self { value, min, max } = *self;
+        match self.kind() {
+            OutOfRangeKind::BelowMin => write!(f, "value {value} is below the minimum {min} of the range {min}..={max}"),
+            OutOfRangeKind::AboveMax => write!(f, "value {value} is above the maximum {max} of the range {min}..={max}"),
+        }
+    }
+}
+
+impl core::error::Error for OutOfRangeError {}
+
+impl<const MIN: irang, const MAX: irang> Ranged<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    // Same as `new`, but reports the reason of failure
+    pub(crate) const fn try_new(n: irang) -> Result<Self, OutOfRangeError> {
+        match Self::new(n) {
+            Some(r) => Ok(r),
+            None => Err(OutOfRangeError { value: n, min: MIN, max: MAX }),
+        }
+    }
+}
+
+// `From<int>` for the types that always fit can not coexist with these impls due to
+// the blanket `TryFrom` implementation in core, so the infallible conversion is
+// provided by `int.as_ranged().expand()` instead.
+macro_rules! int_ranged_try_from {
+    ($($t: ident)+) => {
+        $(
+            impl<const MIN: irang, const MAX: irang> TryFrom<$t> for Ranged<MIN, MAX>
+            where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+            {
+                type Error = OutOfRangeError;
+
+                fn try_from(value: $t) -> Result<Self, Self::Error> {
+                    #![allow(clippy::cast_lossless)]
+                    Self::try_new(value as irang)
+                }
+            }
+        )+
+    };
+}
+
+int_ranged_try_from! {i8 u8 i16 u16 i32 u32 i64 u64 i128 isize usize}
 
 macro_rules! signed_ranged_rem {
     ($($t: ident)+) => {
@@ -361,8 +766,87 @@ where
     }
 }
 
-#[derive(Debug)]
-pub struct ParseRangedError;
+/// The error of parsing a [`Ranged`] from a string
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// assert_eq!("abc".parse::<Ranged<1, 100>>(), Err(ParseRangedError::Invalid));
+/// assert_eq!("1000".parse::<Ranged<1, 100>>(), Err(ParseRangedError::AboveMax { max: 100 }));
+/// assert_eq!("0".parse::<Ranged<1, 100>>().unwrap_err().to_string(), "number is below the minimum 1");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseRangedError {
+    /// The string is not an integer number
+    Invalid,
+    /// The number is less than `MIN`
+    BelowMin {
+        /// The lower bound of the target type
+        min: irang
+    },
+    /// The number is greater than `MAX`
+    AboveMax {
+        /// The upper bound of the target type
+        max: irang
+    },
+}
+
+impl core::fmt::Display for ParseRangedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Invalid => write!(f, "string is not a valid integer"),
+            Self::BelowMin { min } => write!(f, "number is below the minimum {min}"),
+            Self::AboveMax { max } => write!(f, "number is above the maximum {max}"),
+        }
+    }
+}
+
+impl core::error::Error for ParseRangedError {}
+
+impl From<OutOfRangeError> for ParseRangedError {
+    fn from(e: OutOfRangeError) -> Self {
+        match e.kind() {
+            OutOfRangeKind::BelowMin => Self::BelowMin { min: e.min() },
+            OutOfRangeKind::AboveMax => Self::AboveMax { max: e.max() },
+        }
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> Ranged<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    /// Parse a `Ranged` from a string in the given base
+    ///
+    /// Follows the rules of [`i128::from_str_radix`].
+    /// A number too large for `i128` is reported as out of bounds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseRangedError`] if the string is not a number or
+    /// the number is out of the `MIN..=MAX` bounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range from 2 to 36.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!(Ranged::<0, 255>::from_str_radix("ff", 16), Ok(r!([] 255)));
+    /// assert_eq!(Ranged::<0, 255>::from_str_radix("777", 8), Err(ParseRangedError::AboveMax { max: 255 }));
+    /// ```
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseRangedError> {
+        use core::num::IntErrorKind;
+        match irang::from_str_radix(s, radix) {
+            Ok(a) => Ok(Self::try_new(a)?),
+            Err(e) => Err(match e.kind() {
+                IntErrorKind::PosOverflow => ParseRangedError::AboveMax { max: MAX },
+                IntErrorKind::NegOverflow => ParseRangedError::BelowMin { min: MIN },
+                _ => ParseRangedError::Invalid,
+            }),
+        }
+    }
+}
 
 impl<const MIN: irang, const MAX: irang> FromStr for Ranged<MIN,MAX>
 where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
@@ -370,7 +854,6 @@ where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
     type Err = ParseRangedError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let a : irang = s.parse().ok().ok_or(ParseRangedError)?;
-        Self::new(a).ok_or(ParseRangedError)
+        Self::from_str_radix(s, 10)
     }
 }
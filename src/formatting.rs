@@ -0,0 +1,168 @@
+// Formatting traits for Ranged. The radix and exponent formatting follows the behavior
+// of the primitive type the Ranged is stored as, so the negative values are printed
+// in two's complement of the storage width.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+use core::fmt::{self, Write};
+use crate::{Assert, IsAllowed, OperationPossibility, Ranged, allow_range, irang, memlayout};
+use crate::conversions::converter_checkers;
+
+// Bind the value to `$x` converted to the primitive the Ranged is stored as. The order
+// of the checks follows `memlayout`, and the "constant" Ranged is treated as the
+// smallest primitive fitting its value.
+macro_rules! with_repr {
+    ($v:expr, $min:expr, $max:expr, |$x:ident| $body:expr) => {{
+        let v: irang = $v;
+        let fits = |checker: fn(irang, irang) -> OperationPossibility| checker($min, $max) == OperationPossibility::Allowed;
+        if fits(converter_checkers::u8) { let $x = v as u8; $body }
+        else if fits(converter_checkers::i8) { let $x = v as i8; $body }
+        else if fits(converter_checkers::u16) { let $x = v as u16; $body }
+        else if fits(converter_checkers::i16) { let $x = v as i16; $body }
+        else if fits(converter_checkers::u32) { let $x = v as u32; $body }
+        else if fits(converter_checkers::i32) { let $x = v as i32; $body }
+        else if fits(converter_checkers::u64) { let $x = v as u64; $body }
+        else { let $x = v as i64; $body }
+    }};
+}
+
+macro_rules! fmt_as_repr {
+    ($($tr:ident)+) => {
+        $(
+            impl<const MIN: irang, const MAX: irang> fmt::$tr for Ranged<MIN, MAX>
+            where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    with_repr!(self.get(), MIN, MAX, |x| fmt::$tr::fmt(&x, f))
+                }
+            }
+        )+
+    };
+}
+
+fmt_as_repr! {LowerHex UpperHex Octal Binary LowerExp UpperExp}
+
+// Number of digits of `n` in the given radix
+const fn digits(mut n: u128, radix: u128) -> usize {
+    let mut d = 1;
+    while n >= radix {
+        n /= radix;
+        d += 1;
+    }
+    d
+}
+
+// The zero-padded digits are written here first, then passed to `Formatter::pad_integral`
+// to apply the sign, the prefix and the width, fill and alignment flags. The longest
+// output is the binary `i64`, or the 39 decimal digits of `u128`.
+struct DigitBuf {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl DigitBuf {
+    const fn new() -> Self {
+        Self { bytes: [0; 64], len: 0 }
+    }
+
+    const fn as_str(&self) -> &str {
+        // Only the `str` pieces are written
+        unsafe{core::str::from_utf8_unchecked(&self.bytes[..self.len])}
+    }
+}
+
+impl fmt::Write for DigitBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes.get_mut(self.len..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// A `Ranged` formatted with the zero padding implied by its bounds
+///
+/// Created by [`Ranged::padded`]. Implements [`Display`](fmt::Display) and the radix
+/// formatting traits, each padding the value to the number of digits needed for
+/// the widest value of `MIN..=MAX`. The negative values are prefixed with `-` in
+/// decimal, and are printed in two's complement of the storage width in other radixes.
+///
+/// The formatter flags apply to the padded digits like to a primitive integer: the
+/// width, fill and alignment pad the whole output, `+` prints the sign of the
+/// non-negative values and `#` adds the radix prefix.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let x = r!([0 999] 7);
+/// assert_eq!(format!("{}", x.padded()), "007");
+///
+/// let reg = r!([0 0xFFFF] 0xAB);
+/// assert_eq!(format!("{:X}", reg.padded()), "00AB");
+/// assert_eq!(format!("[{:>6}]", x.padded()), "[   007]");
+/// assert_eq!(format!("{:#X}", reg.padded()), "0x00AB");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Padded<const MIN: irang, const MAX: irang>(Ranged<MIN, MAX>)
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed;
+
+impl<const MIN: irang, const MAX: irang> Ranged<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    /// Format the value zero-padded to the maximum digit count implied by the bounds
+    #[must_use]
+    pub const fn padded(self) -> Padded<MIN, MAX> {
+        Padded(self)
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> Padded<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    // The padding width for the given radix; `bits_per_digit` is used for
+    // the two's complement output of negative ranges
+    fn width(radix: u128, bits_per_digit: usize) -> usize {
+        if MIN < 0 {
+            let bits = with_repr!(0, MIN, MAX, |x| 8*core::mem::size_of_val(&x));
+            bits.div_ceil(bits_per_digit)
+        } else {
+            digits(MAX.unsigned_abs(), radix)
+        }
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> fmt::Display for Padded<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let w = digits(MIN.unsigned_abs().max(MAX.unsigned_abs()), 10);
+        let v = self.0.get();
+        let mut buf = DigitBuf::new();
+        write!(buf, "{:0w$}", v.unsigned_abs())?;
+        f.pad_integral(v >= 0, "", buf.as_str())
+    }
+}
+
+macro_rules! padded_radix {
+    ($($tr:ident $spec:literal $prefix:literal $radix:literal $bits:literal)+) => {
+        $(
+            impl<const MIN: irang, const MAX: irang> fmt::$tr for Padded<MIN, MAX>
+            where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+            {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let w = Self::width($radix, $bits);
+                    let mut buf = DigitBuf::new();
+                    with_repr!(self.0.get(), MIN, MAX, |x| write!(buf, $spec, x, w = w))?;
+                    f.pad_integral(true, $prefix, buf.as_str())
+                }
+            }
+        )+
+    };
+}
+
+padded_radix! {
+    LowerHex "{:0w$x}" "0x" 16 4
+    UpperHex "{:0w$X}" "0x" 16 4
+    Octal "{:0w$o}" "0o" 8 3
+    Binary "{:0w$b}" "0b" 2 1
+}
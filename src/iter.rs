@@ -4,10 +4,21 @@ use super::{Assert, IsAllowed, OperationPossibility, Ranged, irang, memlayout};
 use core::convert::TryFrom;
 
 /// An iterator through given range
-pub struct Iter<const MIN: irang, const MAX: irang> 
+///
+/// Iterates in both directions, so `.rev()` counts down:
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let countdown: Vec<i32> = r!(1..=3).into_iter().rev().map(Ranged::i32).collect();
+/// assert_eq!(countdown, [3, 2, 1]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Iter<const MIN: irang, const MAX: irang>
 where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
 {
-    pub(crate) current: Option<Ranged<MIN, MAX>>
+    // The remaining values are `front..=back`, none if `front > back`
+    pub(crate) front: irang,
+    pub(crate) back: irang,
 }
 
 /// Const inclusive range for iterators with `Ranged` output and array indexing
@@ -22,7 +33,7 @@ where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
 /// fibonacci[0] = 1;
 /// fibonacci[1] = 1;
 /// for i in r!(2..=9) {
-///     fibonacci[i.expand()] = fibonacci[(i-r!(1)).expand()] + fibonacci[(i-r!(2)).expand()];
+///     fibonacci[i] = fibonacci[i-r!(1)] + fibonacci[i-r!(2)];
 /// }
 ///
 /// let fib2345: [_; 4] = fibonacci[r!(2..=5)];
@@ -39,7 +50,45 @@ where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
     type Item = Ranged<MIN, MAX>;
     type IntoIter = Iter<MIN, MAX>;
     fn into_iter(self) -> Self::IntoIter {
-        Self::IntoIter{current: Some(unsafe{Ranged::unchecked_new(MIN)})}
+        Self::IntoIter{front: MIN, back: MAX}
+    }
+}
+
+/// Const exclusive range `START..END` for iterators with `Ranged` output and array indexing
+///
+/// The range must not be empty. The output type is `Ranged<START, {END-1}>`.
+/// Do not use directly, use [`r!`](macro.r.html) macro instead
+///
+/// # Example
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// const N: usize = 6;
+/// let mut squares = [0; N];
+/// for i in r!(0..N as i128) {  // Every index of [T; N]
+///     squares[i] = i.i32() * i.i32();
+/// }
+/// assert_eq!(squares[r!(2..5)], [4, 9, 16]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ConstRange<const START: irang, const END: irang>
+where Assert<{allow_range(memlayout(START, END - 1))}>: IsAllowed, ;
+
+impl<const START: irang, const END: irang> IntoIterator for ConstRange<START, END>
+where Assert<{allow_range(memlayout(START, END - 1))}>: IsAllowed,
+{
+    type Item = Ranged<START, {END - 1}>;
+    type IntoIter = Iter<START, {END - 1}>;
+    fn into_iter(self) -> Self::IntoIter {
+        Self::IntoIter{front: START, back: END - 1}
+    }
+}
+
+impl<const START: irang, const END: irang> From<ConstRange<START, END>> for ConstInclusiveRange<START, {END - 1}>
+where Assert<{allow_range(memlayout(START, END - 1))}>: IsAllowed,
+{
+    fn from(_: ConstRange<START, END>) -> Self {
+        Self
     }
 }
 
@@ -49,45 +98,61 @@ where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
     type Item = Ranged<MIN, MAX>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        unsafe{
-            let curr = self.current?;
-            let cval = curr.get();
-            self.current = if cval == MAX {None} else {Ranged::unchecked_new(cval+1).into()};
-            Some(curr)
+        if self.front > self.back {
+            return None;
         }
+        let out = unsafe{Ranged::unchecked_new(self.front)};
+        self.front += 1;
+        Some(out)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let range = MAX-MIN;
-        usize::try_from(range).map_or((usize::MAX, None), |rangeus| (rangeus, Some(rangeus)))
+        let remaining = if self.front > self.back {0} else {self.back - self.front + 1};
+        usize::try_from(remaining).map_or((usize::MAX, None), |rangeus| (rangeus, Some(rangeus)))
     }
 
     #[inline]
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
-        let cval = self.current?;
-        if let Some(out) = Ranged::new(cval.get() + n as i128) {
-            self.current = Some(out);
-            self.next()
-        } else {
-            self.current = None;
-            None
-        }
+        self.front = self.front.saturating_add(n as i128).min(self.back + 1);
+        self.next()
     }
 
-    fn last(self) -> Option<Self::Item> {
-        self.current?;
-        Some(unsafe{Ranged::unchecked_new(MAX)})
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
     }
 
     fn min(mut self) -> Option<Self::Item> {
         self.next()
     }
 
-    fn max(self) -> Option<Self::Item> {
-        self.last()
+    fn max(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> DoubleEndedIterator for Iter<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front > self.back {
+            return None;
+        }
+        let out = unsafe{Ranged::unchecked_new(self.back)};
+        self.back -= 1;
+        Some(out)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.back = self.back.saturating_sub(n as i128).max(self.front - 1);
+        self.next_back()
     }
 }
 
+impl<const MIN: irang, const MAX: irang> core::iter::FusedIterator for Iter<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{}
+
 #[doc(hidden)]
 pub const fn range_fits_usize(min: irang, max: irang) -> OperationPossibility {
     allow_if((max-min) < (usize::MAX as i128))
@@ -96,6 +161,296 @@ pub const fn range_fits_usize(min: irang, max: irang) -> OperationPossibility {
 impl<const MIN: irang, const MAX: irang> ExactSizeIterator for Iter<MIN, MAX>
 where
     Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
-    Assert<{range_fits_usize(MAX, MIN)}>: IsAllowed,
+    Assert<{range_fits_usize(MIN, MAX)}>: IsAllowed,
 {}
 
+/// An iterator through given range in decreasing order
+///
+/// Created by [`Ranged::iter_down`]. The reversed range iterator, constructible in the constant contexts.
+#[derive(Clone, Debug)]
+pub struct IterDown<const MIN: irang, const MAX: irang>(pub(crate) Iter<MIN, MAX>)
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed;
+
+impl<const MIN: irang, const MAX: irang> Iterator for IterDown<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    type Item = Ranged<MIN, MAX>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth_back(n)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn min(mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn max(mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> DoubleEndedIterator for IterDown<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n)
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> core::iter::FusedIterator for IterDown<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{}
+
+impl<const MIN: irang, const MAX: irang> ExactSizeIterator for IterDown<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    Assert<{range_fits_usize(MIN, MAX)}>: IsAllowed,
+{}
+
+// The values `start..end` are consecutive integers, so the steps are the value differences
+impl<const MIN: irang, const MAX: irang> core::iter::Step for Ranged<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        if start > end {
+            return (0, None);
+        }
+        usize::try_from(end.get() - start.get()).map_or((usize::MAX, None), |steps| (steps, Some(steps)))
+    }
+
+    fn forward_checked(start: Self, count: usize) -> Option<Self> {
+        Self::new(start.get().checked_add(irang::try_from(count).ok()?)?)
+    }
+
+    fn backward_checked(start: Self, count: usize) -> Option<Self> {
+        Self::new(start.get().checked_sub(irang::try_from(count).ok()?)?)
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> From<ConstInclusiveRange<MIN, MAX>> for core::ops::RangeInclusive<Ranged<MIN, MAX>>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    fn from(_: ConstInclusiveRange<MIN, MAX>) -> Self {
+        unsafe{Ranged::unchecked_new(MIN)..=Ranged::unchecked_new(MAX)}
+    }
+}
+
+/// Convert the runtime range covering all the values of `Ranged<MIN, MAX>`
+///
+/// Gives the range back if it is not `MIN..=MAX` or it is exhausted.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let lo = Ranged::<0, 9>::new(0).unwrap();
+/// let hi = Ranged::<0, 9>::new(9).unwrap();
+/// let full = ConstInclusiveRange::try_from(lo..=hi).unwrap();
+/// let digits: [u8; 10] = core::array::from_fn(|i| i as u8);
+/// assert_eq!(digits[full], digits);
+///
+/// assert!(ConstInclusiveRange::try_from(lo..=hi.pred().unwrap()).is_err());
+/// ```
+impl<const MIN: irang, const MAX: irang> TryFrom<core::ops::RangeInclusive<Ranged<MIN, MAX>>> for ConstInclusiveRange<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    type Error = core::ops::RangeInclusive<Ranged<MIN, MAX>>;
+
+    fn try_from(range: core::ops::RangeInclusive<Ranged<MIN, MAX>>) -> Result<Self, Self::Error> {
+        if range.start().get() == MIN && range.end().get() == MAX && !range.is_empty() {
+            Ok(Self)
+        } else {
+            Err(range)
+        }
+    }
+}
+
+#[doc(hidden)]
+#[must_use]
+pub const fn product_fits_usize(size1: irang, size2: irang, size3: irang, size4: irang) -> OperationPossibility {
+    let Some(total) = size1.checked_mul(size2) else { return allow_if(false) };
+    let Some(total) = total.checked_mul(size3) else { return allow_if(false) };
+    let Some(total) = total.checked_mul(size4) else { return allow_if(false) };
+    allow_if(total <= usize::MAX as irang)
+}
+
+// The product iterators count the tuples with a single index in `front..back`.
+// The tuples are in the row-major order, the last dimension changes first, and
+// the index is decoded with the division by the sizes of the next dimensions.
+// The total count fits usize, it is checked by the `cross` methods creating them.
+// Adding a dimension scales the index, so the remaining tuples are kept.
+macro_rules! product_iter {
+    ($(#[$attr:meta])* $name:ident, $($var:ident $min:ident $max:ident),+) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug)]
+        pub struct $name<$(const $min: irang, const $max: irang),+>
+        where $(Assert<{allow_range(memlayout($min, $max))}>: IsAllowed,)+
+        {
+            front: usize,
+            back: usize,
+        }
+
+        #[allow(clippy::cast_sign_loss)]
+        impl<$(const $min: irang, const $max: irang),+> $name<$($min, $max),+>
+        where $(Assert<{allow_range(memlayout($min, $max))}>: IsAllowed,)+
+        {
+            #[allow(clippy::cast_possible_wrap)]
+            const fn tuple(index: usize) -> ($(Ranged<$min, $max>,)+) {
+                let mut stride = (1 $(* ($max - $min + 1))+) as usize;
+                $(
+                    stride /= ($max - $min + 1) as usize;
+                    let $var = unsafe{Ranged::unchecked_new($min + ((index / stride) % ($max - $min + 1) as usize) as irang)};
+                )+
+                ($($var,)+)
+            }
+        }
+
+        impl<$(const $min: irang, const $max: irang),+> Iterator for $name<$($min, $max),+>
+        where $(Assert<{allow_range(memlayout($min, $max))}>: IsAllowed,)+
+        {
+            type Item = ($(Ranged<$min, $max>,)+);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.front += 1;
+                Some(Self::tuple(self.front - 1))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let n = self.back - self.front;
+                (n, Some(n))
+            }
+
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.front = self.front.saturating_add(n).min(self.back);
+                self.next()
+            }
+
+            fn last(mut self) -> Option<Self::Item> {
+                self.next_back()
+            }
+        }
+
+        impl<$(const $min: irang, const $max: irang),+> DoubleEndedIterator for $name<$($min, $max),+>
+        where $(Assert<{allow_range(memlayout($min, $max))}>: IsAllowed,)+
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                if self.front == self.back {
+                    return None;
+                }
+                self.back -= 1;
+                Some(Self::tuple(self.back))
+            }
+
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.back = self.back.saturating_sub(n).max(self.front);
+                self.next_back()
+            }
+        }
+
+        impl<$(const $min: irang, const $max: irang),+> ExactSizeIterator for $name<$($min, $max),+>
+        where $(Assert<{allow_range(memlayout($min, $max))}>: IsAllowed,)+
+        {}
+
+        impl<$(const $min: irang, const $max: irang),+> core::iter::FusedIterator for $name<$($min, $max),+>
+        where $(Assert<{allow_range(memlayout($min, $max))}>: IsAllowed,)+
+        {}
+    };
+}
+
+product_iter!(
+    /// An iterator over the pairs of two ranges, created by [`ConstInclusiveRange::cross`]
+    Product2, a A0 A1, b B0 B1
+);
+product_iter!(
+    /// An iterator over the triples of three ranges, created by [`Product2::cross`]
+    Product3, a A0 A1, b B0 B1, c C0 C1
+);
+product_iter!(
+    /// An iterator over the quadruples of four ranges, created by [`Product3::cross`]
+    Product4, a A0 A1, b B0 B1, c C0 C1, d D0 D1
+);
+
+#[allow(clippy::cast_sign_loss)]
+impl<const MIN: irang, const MAX: irang> ConstInclusiveRange<MIN, MAX>
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    /// Iterate over the pairs of the values of two ranges, the last one changing first
+    ///
+    /// The iterator has the exact size and is double-ended. Chain `cross`
+    /// calls for up to four ranges.
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let grid = [[1, 2, 3], [4, 5, 6]];
+    /// let cells = r!(0..=1).cross(r!(0..=2));
+    /// assert_eq!(cells.len(), 6);
+    /// assert_eq!(cells.map(|(i, j)| grid[i][j]).sum::<i32>(), 21);
+    ///
+    /// let (i, j, k) = r!(0..=1).cross(r!(0..=2)).cross(r!(0..=3)).next_back().unwrap();
+    /// assert_eq!((i.get(), j.get(), k.get()), (1, 2, 3));
+    /// ```
+    #[must_use]
+    pub const fn cross<const B0: irang, const B1: irang>(self, _other: ConstInclusiveRange<B0, B1>) -> Product2<MIN, MAX, B0, B1>
+    where
+        Assert<{allow_range(memlayout(B0, B1))}>: IsAllowed,
+        Assert<{product_fits_usize(MAX-MIN+1, B1-B0+1, 1, 1)}>: IsAllowed,
+    {
+        Product2 { front: 0, back: ((MAX-MIN+1) * (B1-B0+1)) as usize }
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl<const A0: irang, const A1: irang, const B0: irang, const B1: irang> Product2<A0, A1, B0, B1>
+where
+    Assert<{allow_range(memlayout(A0, A1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(B0, B1))}>: IsAllowed,
+{
+    /// Add the third range to the product, keeping the remaining pairs
+    #[must_use]
+    pub const fn cross<const C0: irang, const C1: irang>(self, _other: ConstInclusiveRange<C0, C1>) -> Product3<A0, A1, B0, B1, C0, C1>
+    where
+        Assert<{allow_range(memlayout(C0, C1))}>: IsAllowed,
+        Assert<{product_fits_usize(A1-A0+1, B1-B0+1, C1-C0+1, 1)}>: IsAllowed,
+    {
+        let size = (C1-C0+1) as usize;
+        Product3 { front: self.front * size, back: self.back * size }
+    }
+}
+
+#[allow(clippy::cast_sign_loss)]
+impl<const A0: irang, const A1: irang, const B0: irang, const B1: irang, const C0: irang, const C1: irang> Product3<A0, A1, B0, B1, C0, C1>
+where
+    Assert<{allow_range(memlayout(A0, A1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(B0, B1))}>: IsAllowed,
+    Assert<{allow_range(memlayout(C0, C1))}>: IsAllowed,
+{
+    /// Add the fourth range to the product, keeping the remaining triples
+    #[must_use]
+    pub const fn cross<const D0: irang, const D1: irang>(self, _other: ConstInclusiveRange<D0, D1>) -> Product4<A0, A1, B0, B1, C0, C1, D0, D1>
+    where
+        Assert<{allow_range(memlayout(D0, D1))}>: IsAllowed,
+        Assert<{product_fits_usize(A1-A0+1, B1-B0+1, C1-C0+1, D1-D0+1)}>: IsAllowed,
+    {
+        let size = (D1-D0+1) as usize;
+        Product4 { front: self.front * size, back: self.back * size }
+    }
+}
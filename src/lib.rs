@@ -59,7 +59,9 @@
 //!    - [Ranged -> Ranged conversion](#ranged---ranged-conversion)
 //!    - [int -> Ranged conversion](#int---ranged-conversion)
 //!    - [Ranged -> int conversion](#ranged---int-conversion)
+//!    - [Ranged and floats](#ranged-and-floats)
 //! * [Array indexing, slicing and iteration](#array-indexing-slicing-and-iteration)
+//! * [Containers](#containers)
 //! * [Comparison](#comparison)
 //! * [Arithmetics](#arithmetics)
 //! * [Pattern matching and case analysis](#pattern-matching-and-case-analysis)
@@ -184,6 +186,22 @@
 //! use ranged_integers::AsRanged;
 //! let x = 15_u8.as_ranged();  // Ranged<0, 255>
 //! let y = 15_i16.as_ranged(); // Ranged<-32768, 32767>
+//! let b = true.as_ranged();    // Ranged<0, 1>
+//! let c = 'A'.as_ranged();     // Ranged<0, 0x10FFFF>
+//!
+//! let z: Ranged<0, 1000> = 15_u8.as_ranged().expand();  // Infallible, u8 always fits 0..=1000
+//! ```
+//!
+//! Way 4: use [`TryFrom`] with a descriptive [`OutOfRangeError`]
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! fn percent(x: u16) -> Result<Ranged<0, 100>, OutOfRangeError> {
+//!     let p = Ranged::try_from(x)?;
+//!     Ok(p)
+//! }
+//! assert_eq!(percent(42), Ok(r!([] 42)));
+//! assert_eq!(percent(420).unwrap_err().kind(), OutOfRangeKind::AboveMax);
 //! ```
 //!
 //! ### `Ranged` -> `int` conversion
@@ -221,24 +239,75 @@
 //! let err = x.i8();  // Error: 0..=200 doesn't fit i8
 //! ```
 //!
+//! The generic [`to`](Ranged::to) method is a shortcut for `From`, and
+//! [`try_to`](Ranged::try_to) converts to any integer primitive checking the value at runtime.
+//! `TryFrom<Ranged>` can't be implemented for the primitives due to the blanket `TryFrom`
+//! implementation in core conflicting with `From`.
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! let x = r!([-10 300] 20);
+//! let y: u8 = x.try_to().unwrap();  // -10..=300 partially fits u8
+//! assert_eq!(r!([-10 300] 300).try_to::<u8>().ok(), None);
+//! let z = x.to::<i16>();  // -10..=300 fits i16
+//! ```
+//!
+//! ### `Ranged` and floats
+//!
+//! `f32::From` and `f64::From` are implemented when every value of the range
+//! is exactly representable in the float (`2^24` and `2^53` by absolute value).
+//! The floats are converted to `Ranged` with [`Ranged::from_f64_round`] and
+//! [`Ranged::from_f64_saturating`] according to the [`RoundingMode`].
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! let adc: Ranged<0, 4095> = r!([] 2048);
+//! let volts = f32::from(adc) * 3.3 / 4095.0;
+//! let percent = Ranged::<0, 100>::from_f64_saturating(f64::from(volts) * 30.0, RoundingMode::Nearest);
+//! assert_eq!(percent, 50);
+//! ```
+//!
 //! ## Array indexing, slicing and iteration
 //!
 //! The [`ConstInclusiveRange<MIN,MAX>`] zero-size type is a range `MIN..=MAX`
 //! capable to create the iterator ([`IntoIterator`] trait implemented)
 //! with `Ranged<MIN, MAX>` output type. The [`r!`] macro can be used instead.
+//! The [`ConstRange<START,END>`] is the exclusive range `START..END` with
+//! `Ranged<START, {END-1}>` output, created with `r!(START..END)`; `r!(0..N as i128)` covers
+//! every index of `[T; N]`.
+//! The [`cross`](ConstInclusiveRange::cross) method iterates over the tuples of
+//! up to four ranges in the nested loops order, e.g. `r!(0..=2).cross(r!(0..=2))`
+//! over the `(Ranged<0, 2>, Ranged<0, 2>)` cells of a 3x3 grid.
 //!
 //! The [`Ranged::iter_up`](struct.Ranged.html#method.iter_up) method creates an
-//! iterator from the current value up to `MAX`.
+//! iterator from the current value up to `MAX`, and
+//! [`Ranged::iter_down`](struct.Ranged.html#method.iter_down) from the current value
+//! down to `MIN`. The iterators are double-ended, so `r!(0..=9).into_iter().rev()` counts down.
+//!
+//! `Ranged` implements [`Step`](core::iter::Step), so the runtime ranges `lo..hi` and `lo..=hi`
+//! iterate over `Ranged` values. The [`succ`](Ranged::succ) and [`pred`](Ranged::pred) methods
+//! return the neighbor values, if any.
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! fn sum_between(arr: &[u32; 100], lo: Ranged<0, 99>, hi: Ranged<0, 99>) -> u32 {
+//!     (lo..=hi).map(|i| arr[i]).sum()  // No bounds checks
+//! }
+//! assert_eq!(sum_between(&[1; 100], r!([0 99] 10), r!([0 99] 19)), 10);
+//! ```
 //!
-//! The arrays `[T; N]` may be indexed with `Ranged<0, {N-1}>` and sliced
-//! with `r!(MIN..END)` range with a reference to fixed-size array output.
+//! The arrays `[T; N]` may be indexed with any `Ranged<MIN, MAX>` fitting `0..N` and sliced
+//! with `r!(MIN..=MAX)` range with a reference to fixed-size array output.
+//! The arrays of arrays are indexed with [`Idx2`] and [`Idx3`] holding a `Ranged` per dimension.
 //!
 //! ```
 //! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*; fn move_player(dice_roll: Ranged<1, 6>) {}
 //! let arr = [r!([1 6] 2), r!([] 3), r!([] 4), r!([] 5)];
 //!
 //! assert_eq!(arr[r!(1..=2)], [3,4]);  // Slicing with array reference output
-//! assert_eq!(arr[r!([0 3] 1)], 3);  // Slicing with array reference output
+//! assert_eq!(arr[r!([0 3] 1)], 3);  // Indexing with Ranged<0, 3>
+//! assert_eq!(arr[r!([1 2] 1)], 3);  // Any range inside 0..4 is accepted
+//! assert_eq!(arr[r!(3)], 5);
 //!
 //! // Not recommended to use this:
 //!     for i in ConstInclusiveRange::<0, 3> {
@@ -255,6 +324,121 @@
 //! }
 //! ```
 //!
+//! The fieldless enums are mapped to `Ranged` over their discriminants with
+//! `#[derive(RangedEnum)]`, available with the `derive` feature:
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! # #[cfg(not(feature = "derive"))] fn main() {}
+//! # #[cfg(feature = "derive")] fn main() {
+//! #[derive(RangedEnum, Clone, Copy)]
+//! enum Month { Jan, Feb, Mar, Apr, May, Jun, Jul, Aug, Sep, Oct, Nov, Dec }
+//!
+//! let days = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+//! assert_eq!(Month::COUNT, 12);
+//! assert_eq!(days[Ranged::from(Month::Feb)], 28);  // Ranged<0, 11>
+//! let m: Month = r!([0 11] 2).into();  // Infallible, the discriminants are contiguous
+//! # }
+//! ```
+//!
+//! The [`ArrayExt`] trait provides [`window`](ArrayExt::window) with a compile-time length
+//! and a runtime `Ranged` offset, and the compile-time checked
+//! [`split_fixed`](ArrayExt::split_fixed), [`concat_fixed`](ArrayExt::concat_fixed),
+//! [`chunks_fixed`](ArrayExt::chunks_fixed) and [`chunk`](ArrayExt::chunk).
+//! The search methods like [`position_ranged`](ArrayExt::position_ranged) and
+//! [`max_index`](ArrayExt::max_index) return `Ranged` indices for the same array.
+//! The [`array_from_fn`] function creates an array from a function of the `Ranged` index,
+//! also at compile time.
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! let signal = [0, 1, 4, 9, 16, 25];
+//! for start in r!(0..=4) {
+//!     let [a, b] = *signal.window::<2>(start);  // No bounds check
+//!     assert!(b >= a);
+//! }
+//! ```
+//!
+//! ## Containers
+//!
+//! [`BoundedVec<T, CAP>`](BoundedVec) is a stack-allocated vector with `Ranged<0, CAP>` length.
+//! The `push` and `pop` methods return the new length.
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! let mut stack = BoundedVec::<u8, 16>::new();
+//! stack.push(1).unwrap();
+//! let depth: Ranged<0, 16> = stack.push(2).unwrap();
+//! assert_eq!(depth, 2);
+//! ```
+//!
+//! [`RangedMap<MIN, MAX, V>`](RangedMap) maps every value of `Ranged<MIN, MAX>` to `V`, storing
+//! the values in an array. The keys may start at any `MIN`.
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! let mut histogram = RangedMap::<-40, 125, u32>::default();
+//! let temperature: Ranged<-40, 125> = r!([] 21);
+//! histogram[temperature] += 1;  // No bounds check
+//! ```
+//!
+//! The [`RangedTable`] alias names the maps computed at compile time with
+//! [`from_const_fn`](RangedMap::from_const_fn); this needs `#![feature(const_trait_impl)]`.
+//!
+//! ```
+//! #![feature(adt_const_params, generic_const_exprs, const_trait_impl)]
+//! use ranged_integers::*;
+//!
+//! const fn hex_digit(x: Ranged<0, 15>) -> char {
+//!     let v = x.get() as u8;
+//!     if v < 10 { (b'0' + v) as char } else { (b'a' + v - 10) as char }
+//! }
+//! const HEX: RangedTable<0, 15, char> = RangedTable::from_const_fn(hex_digit);
+//! assert_eq!(HEX[r!([0 15] 12)], 'c');
+//! ```
+//!
+//! [`RangedSet<MIN, MAX>`](RangedSet) is a bitset of `Ranged<MIN, MAX>` values with the
+//! set algebra operators `|`, `&`, `-`, `^` and `!`.
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! let mut channels = RangedSet::<0, 63>::new();
+//! channels.insert(r!(5));
+//! channels.insert(r!(42));
+//! let count: Ranged<0, 64> = channels.len();
+//! assert_eq!(count, 2);
+//! ```
+//!
+//! [`Permutation<N>`](Permutation) is a permutation of `0..N` keeping `[Ranged<0, {N-1}>; N]`.
+//! The bijectivity is checked on construction, so the indices taken from it need no bounds check.
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! let rotate = Permutation::new([r!([0 3] 1), r!([] 2), r!([] 3), r!([] 0)]).unwrap();
+//! assert_eq!(rotate.apply([1, 2, 3, 4]), [2, 3, 4, 1]);
+//! assert_eq!(rotate.cycles().count(), 1);
+//! ```
+//!
+//! [`RingBuffer<T, N>`](RingBuffer) is a stack-allocated ring buffer with `Ranged<0, {N-1}>`
+//! head, tail and logical indices, and `Ranged<0, N>` length.
+//!
+//! ```
+//! # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+//! let mut recent = RingBuffer::<i32, 8>::new();
+//! for x in 0..20 {
+//!     recent.push_overwrite(x);
+//! }
+//! assert_eq!(recent[r!([0 7] 0)], 12);
+//! ```
+//!
+//! `BucketQueue<MIN, MAX, T>` is a priority queue with `Ranged<MIN, MAX>`
+//! priorities, available with the `alloc` feature.
+//!
+//! `sort_ranged` and the stable `sort_by_ranged_key` sort in linear time, choosing
+//! counting sort or radix sort from the key bounds. The radix sort allocates, so
+//! `sort_by_ranged_key` needs the `alloc` feature, and `sort_ranged` falls back to
+//! `sort_unstable` without it for the ranges of more than 256 values.
+//!
 //! ## Comparison
 //!
 //! All `Eq` and `Ord` operations between different Ranged types are allowed,
@@ -407,6 +591,8 @@
 #![warn(clippy::pedantic)]
 #![feature(const_index)]
 #![feature(const_trait_impl)]
+#![feature(const_destruct)]  // Dropping the generic closures in const fn
+#![feature(step_trait)]  // Iterating over the runtime ranges of Ranged
 
 // An alias integer representing the public interface of Ranged constants. Introduced
 // to easily change when necessary.
@@ -415,6 +601,7 @@ type irang = i128;
 
 
 #[cfg(test)] #[macro_use] extern crate std;
+#[cfg(feature = "alloc")] extern crate alloc;
 #[cfg(test)] mod tests;
 
 pub mod value_check;  // Compile-time infrastructure for Ranged
@@ -424,9 +611,33 @@ mod conversions;  // Conversions to/from Ranged
 mod arithmetics;  // Arithmetic operations
 mod iter;  // Iterating over a constant range
 mod arrays;  // Implementing Ranged-related logics for arrays indexing and slicing
-
-pub use conversions::{AsRanged, Split, SplitByDifference};
-pub use iter::ConstInclusiveRange;
+mod formatting;  // Radix, exponent and padded formatting
+mod bounded_vec;  // Stack-allocated vector with Ranged length
+mod ranged_map;  // Array-backed map with Ranged keys
+mod ranged_set;  // Bitset over a Ranged domain
+mod permutation;  // Permutations of 0..N
+mod ring_buffer;  // Fixed-capacity ring buffer with Ranged head
+#[cfg(feature = "alloc")]
+mod bucket_queue;  // Priority queue with Ranged priorities
+mod sort;  // Counting and radix sort by Ranged keys
+
+pub use conversions::{AsRanged, OutOfRangeError, OutOfRangeKind, ParseRangedError, RoundingMode, Split, SplitByDifference};
+pub use iter::{ConstInclusiveRange, ConstRange, Product2, Product3, Product4};
+pub use formatting::Padded;
+pub use arrays::{array_from_fn, ArrayExt, EnumerateRanged, Idx2, Idx3};
+pub use bounded_vec::BoundedVec;
+pub use ranged_map::{RangedMap, RangedMapIter, RangedMapIterMut, RangedTable};
+pub use ranged_set::{RangedSet, RangedSetIter};
+pub use permutation::{Cycle, Cycles, Permutation};
+pub use ring_buffer::{RingBuffer, RingBufferIter};
+#[cfg(feature = "alloc")]
+pub use bucket_queue::BucketQueue;
+pub use sort::sort_ranged;
+#[cfg(feature = "alloc")]
+pub use sort::sort_by_ranged_key;
+
+#[cfg(feature = "derive")]
+pub use ranged_integers_derive::RangedEnum;
 
 use value_check::{Assert, IsAllowed, OperationPossibility, memlayout, allow_range, allow_if, allow_creation};
 
@@ -464,9 +675,9 @@ where
         }
     }
 
-    // Convert Ranged to a primitive
+    /// Get the value as `i128`
     #[allow(clippy::inline_always)] #[must_use] #[inline(always)]
-    const fn get(self) -> irang {
+    pub const fn get(self) -> irang {
         if MIN == MAX {MIN}
         else {self.v.to_irang(const{MIN>=0})}
     }
@@ -523,10 +734,48 @@ where
         unsafe { Self::unchecked_new(V) }
     }
 
+    /// The next value, or `None` if the value is `MAX`
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!(r!([0 9] 4).succ().unwrap(), 5);
+    /// assert!(r!([0 9] 9).succ().is_none());
+    /// ```
+    #[must_use]
+    pub const fn succ(self) -> Option<Self> {
+        let v = self.get();
+        if v == MAX { None } else { Some(unsafe { Self::unchecked_new(v + 1) }) }
+    }
+
+    /// The previous value, or `None` if the value is `MIN`
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// assert_eq!(r!([0 9] 4).pred().unwrap(), 3);
+    /// assert!(r!([0 9] 0).pred().is_none());
+    /// ```
+    #[must_use]
+    pub const fn pred(self) -> Option<Self> {
+        let v = self.get();
+        if v == MIN { None } else { Some(unsafe { Self::unchecked_new(v - 1) }) }
+    }
+
     /// Iterate up from current value to `Self::MAX` (inclusively) using `Self` as output
     #[must_use]
     pub const fn iter_up(self) -> iter::Iter<MIN, MAX> {
-        iter::Iter::<MIN,MAX>{current: Some(self)}
+        iter::Iter::<MIN,MAX>{front: self.get(), back: MAX}
+    }
+
+    /// Iterate down from current value to `Self::MIN` (inclusively) using `Self` as output
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let down: Vec<i32> = r!([0 9] 3).iter_down().map(Ranged::i32).collect();
+    /// assert_eq!(down, [3, 2, 1, 0]);
+    /// ```
+    #[must_use]
+    pub const fn iter_down(self) -> iter::IterDown<MIN, MAX> {
+        iter::IterDown(iter::Iter::<MIN,MAX>{front: MIN, back: self.get()})
     }
 }
 
@@ -548,6 +797,18 @@ where
 /// for i in r!(0..=9){
 ///     let v: Ranged<0,9> = i; 
 /// }
+/// // Exclusive range:
+/// for i in r!(0..10){
+///     let v: Ranged<0,9> = i;
+/// }
+/// // The end of both range kinds is an `i128` constant expression:
+/// const N: usize = 10;
+/// for i in r!(0..N as i128){
+///     let v: Ranged<0,9> = i;
+/// }
+/// for i in r!(1..=N as i128 - 1){
+///     let v: Ranged<1,9> = i;
+/// }
 /// ```
 #[macro_export]
 macro_rules! r {
@@ -557,15 +818,18 @@ macro_rules! r {
     ([] $v:expr) => {
         $crate::Ranged::create_const::<$v>()
     };
-    (-$min:tt..=$max:tt) => {
+    (-$min:tt..=$max:expr) => {
         $crate::ConstInclusiveRange::<{-$min}, {$max}>
     };
-    (-$min:tt..=-$max:tt) => {
-        $crate::ConstInclusiveRange::<{-$min}, {-$max}>
-    };
-    ($min:tt..=$max:tt) => {
+    ($min:tt..=$max:expr) => {
         $crate::ConstInclusiveRange::<{$min}, {$max}>
     };
+    (-$min:tt..$max:expr) => {
+        $crate::ConstRange::<{-$min}, {$max}>
+    };
+    ($min:tt..$max:expr) => {
+        $crate::ConstRange::<{$min}, {$max}>
+    };
     ($v:literal) => {
         $crate::Ranged::<$v, $v>::create_const::<$v>()
     };
@@ -751,6 +1015,50 @@ let a = r![[100 1000] 500] / r![[0 6] 5];
 let a = r![[100 1000] 500] / r![[-1 6] 5];
 ```
 
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let _ = bool::from(r!([0 1] 1));
+let _ = char::from(r!([0 0xD7FF] 1));
+let _ = core::num::NonZeroU8::from(r!([1 255] 1));
+let _ = core::num::NonZeroI8::from(r!([1 127] 1));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let _ = bool::from(r!([0 2] 1));
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let _ = f32::from(r!([-16777216 16777216] 1));
+let _ = f64::from(r!([-9007199254740992 9007199254740992] 1));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let _ = f32::from(r!([0 16777217] 1));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let _ = f64::from(r!([-9007199254740993 0] 1));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let _ = char::from(r!([0 0xD800] 1));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let _ = core::num::NonZeroU8::from(r!([0 255] 1));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let _ = core::num::NonZeroI8::from(r!([-1 1] 1));
+```
+
 ```
 # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
 Ranged::<0,1>::new(1);
@@ -954,14 +1262,184 @@ assert!(  r!([10 50] 40).fit_greater_eq(r!([0 10] 5)) == Some(r!([10 50] 40))  )
 ```
 # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
 let arr = [1,2,3,4,5];
-let x = &arr[r!(4).expand()];
+let x = &arr[r!(4)];
 assert_eq!(*x, 5);
 ```
 
 ```compile_fail
 # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
 let arr = [1,2,3,4,5];
-let x = &arr[r!(5).expand()];
+let x = &arr[r!(5)];
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5];
+let x = &arr[r!([2 4] 3)];
+assert_eq!(*x, 4);
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5];
+let x = &arr[r!([2 5] 3)];
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5];
+let x = &arr[r!([-1 4] 3)];
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5];
+let x: &[i32; 2] = arr.window(r!([0 3] 3));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5];
+let x: &[i32; 2] = arr.window(r!([0 4] 3));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5];
+let x: &[i32; 6] = arr.window(r!(0));
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5,6];
+let (a, b) = arr.split_fixed::<6>();
+let c = arr.chunks_fixed::<3>();
+let d = arr.chunk::<2>(r!([0 2] 2));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5,6];
+let (a, b) = arr.split_fixed::<7>();
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5,6];
+let c = arr.chunks_fixed::<4>();
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5,6];
+let d = arr.chunk::<2>(r!([0 3] 3));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr: [i32; 0] = [];
+let i = arr.min_index();
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let grid = [[1,2,3],[4,5,6]];
+let x = grid[Idx2(r!([0 1] 1), r!([0 2] 2))];
+let cube = [grid; 4];
+let y = cube[Idx3(r!(3), r!(1), r!(2))];
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let grid = [[1,2,3],[4,5,6]];
+let x = grid[Idx2(r!([0 2] 1), r!([0 2] 2))];
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let grid = [[1,2,3],[4,5,6]];
+let x = grid[Idx2(r!([0 1] 1), r!([0 3] 2))];
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let cube = [[[1,2,3],[4,5,6]]; 4];
+let y = cube[Idx3(r!(4), r!(1), r!(2))];
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let v = BoundedVec::<u8, 4>::new();
+let x = v.get(r!([0 3] 3));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let v = BoundedVec::<u8, 4>::new();
+let x = v.get(r!([0 4] 3));
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let m = RangedMap::<-5, 5, u8>::default();
+let x = m[r!([-5 -1] -3)];
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let m = RangedMap::<-5, 5, u8>::default();
+let x = m[r!([-6 -1] -3)];
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let m = RangedMap::<-5, 5, u8>::default();
+let x = m.get(r!(6));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let mut s = RangedSet::<1, 9>::new();
+s.insert(r!(10));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let p = Permutation::new([r!([0 2] 2), r!([] 0), r!([] 1)]).unwrap();
+let x = p.apply([1, 2, 3, 4]);
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+for i in r!(3..3) {}
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let p = r!(0..=4294967294).cross(r!(0..=4294967295));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let p = r!(0..=18446744073709551615).cross(r!(0..=1));
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5];
+let x = &arr[r!(3..6)];
+```
+
+```
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let arr = [1,2,3,4,5];
+let x = &arr[r!(3..5)];
+```
+
+```compile_fail
+# #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+let buf = RingBuffer::<u8, 3>::new();
+let x = buf.get(r!([0 3] 3));
 ```
 
 ```
@@ -0,0 +1,223 @@
+// A permutation of `0..N` stored as `[Ranged<0, N-1>; N]`, the image of every index.
+// The values are distinct, so every index of `0..N` appears exactly once.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+#![allow(clippy::cast_sign_loss)]
+
+use core::mem::ManuallyDrop;
+use crate::{allow_range, conversions, irang, memlayout, Assert, IsAllowed, Ranged};
+
+/// A permutation of `0..N`
+///
+/// Holds `[Ranged<0, {N-1}>; N]` with every index appearing exactly once, so the
+/// indices taken from the permutation index `[T; N]` with no bounds check, and
+/// [`apply`](Self::apply) moves every element exactly once.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let p = Permutation::new([r!([0 2] 2), r!([] 0), r!([] 1)]).unwrap();
+/// assert_eq!(p.apply(['a', 'b', 'c']), ['c', 'a', 'b']);
+/// assert_eq!(p.inverse().apply(['c', 'a', 'b']), ['a', 'b', 'c']);
+/// assert!(p.compose(&p.inverse()).is_identity());
+///
+/// // Duplicated indices are rejected
+/// assert!(Permutation::new([r!([0 2] 2), r!([] 0), r!([] 2)]).is_none());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Permutation<const N: usize>
+where
+    // The normalization bound, see the header of arrays.rs
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    // The array indexing with the permutation values
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    map: [Ranged<0, {N as irang - 1}>; N],
+}
+
+impl<const N: usize> Permutation<N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    /// The permutation keeping every index in place
+    #[must_use]
+    pub const fn identity() -> Self {
+        let mut map = [unsafe{Ranged::unchecked_new(0)}; N];
+        let mut i = 0;
+        while i < N {
+            map[i] = unsafe{Ranged::unchecked_new(i as irang)};
+            i += 1;
+        }
+        Self { map }
+    }
+
+    /// Create the permutation mapping `i` to `map[i]`, or `None` if `map` has repeated values
+    #[must_use]
+    pub const fn new(map: [Ranged<0, {N as irang - 1}>; N]) -> Option<Self> {
+        let mut seen = [false; N];
+        let mut i = 0;
+        while i < N {
+            let v = map[i].get() as usize;
+            if seen[v] {
+                return None;
+            }
+            seen[v] = true;
+            i += 1;
+        }
+        Some(Self { map })
+    }
+
+    /// The images of the indices `0..N`
+    #[must_use]
+    pub const fn as_array(&self) -> &[Ranged<0, {N as irang - 1}>; N] {
+        &self.map
+    }
+
+    /// The image of the index `i`
+    #[must_use]
+    pub fn get(&self, i: Ranged<0, {N as irang - 1}>) -> Ranged<0, {N as irang - 1}> {
+        self.map[i]
+    }
+
+    /// Check if the permutation keeps every index in place
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        self.map.iter().enumerate().all(|(i, v)| v.get() == i as irang)
+    }
+
+    /// Rearrange the array, so the element `i` of the output is the element `self[i]` of the input
+    #[must_use]
+    pub fn apply<T>(&self, arr: [T; N]) -> [T; N] {
+        let src = ManuallyDrop::new(arr);
+        // Every element is moved out exactly once, since the indices are distinct
+        core::array::from_fn(|i| unsafe{core::ptr::read(&raw const src[self.map[i]])})
+    }
+
+    /// The permutation `i -> self[other[i]]`
+    ///
+    /// Applying the composition is the same as applying `self`, then `other`.
+    #[must_use]
+    pub fn compose(&self, other: &Self) -> Self {
+        Self { map: other.map.map(|i| self.map[i]) }
+    }
+
+    /// The permutation reverting `self`
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        let mut map = self.map;
+        for (i, &v) in self.map.iter().enumerate() {
+            map[v] = unsafe{Ranged::unchecked_new(i as irang)};
+        }
+        Self { map }
+    }
+
+    /// Iterate over the cycles of the permutation in the order of their smallest elements
+    ///
+    /// ```
+    /// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+    /// let p = Permutation::new([r!([0 4] 1), r!([] 0), r!([] 2), r!([] 4), r!([] 3)]).unwrap();
+    /// let mut cycles = p.cycles().map(|c| c.map(Ranged::i32).collect::<Vec<_>>());
+    /// assert_eq!(cycles.next(), Some(vec![0, 1]));
+    /// assert_eq!(cycles.next(), Some(vec![2]));
+    /// assert_eq!(cycles.next(), Some(vec![3, 4]));
+    /// assert_eq!(cycles.next(), None);
+    /// ```
+    #[must_use]
+    pub const fn cycles(&self) -> Cycles<'_, N> {
+        Cycles { perm: self, visited: [false; N], next: 0 }
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for Permutation<N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Permutation")?;
+        f.debug_list().entries(self.map.iter().map(|v| v.get())).finish()
+    }
+}
+
+/// An iterator over the cycles of [`Permutation`]
+///
+/// Created by [`Permutation::cycles`].
+pub struct Cycles<'a, const N: usize>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    perm: &'a Permutation<N>,
+    visited: [bool; N],
+    next: usize,
+}
+
+impl<'a, const N: usize> Iterator for Cycles<'a, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    type Item = Cycle<'a, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < N && self.visited[self.next] {
+            self.next += 1;
+        }
+        if self.next == N {
+            return None;
+        }
+        let start: Ranged<0, {N as irang - 1}> = unsafe{Ranged::unchecked_new(self.next as irang)};
+        let mut i = start;
+        loop {
+            self.visited[i] = true;
+            i = self.perm.map[i];
+            if i == start {
+                break;
+            }
+        }
+        Some(Cycle { perm: self.perm, start, current: Some(start) })
+    }
+}
+
+/// The elements of a permutation cycle, starting with the smallest one
+///
+/// Created by [`Cycles`].
+pub struct Cycle<'a, const N: usize>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    perm: &'a Permutation<N>,
+    start: Ranged<0, {N as irang - 1}>,
+    current: Option<Ranged<0, {N as irang - 1}>>,
+}
+
+impl<const N: usize> Iterator for Cycle<'_, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    type Item = Ranged<0, {N as irang - 1}>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let out = self.current?;
+        let next = self.perm.map[out];
+        self.current = if next == self.start {None} else {Some(next)};
+        Some(out)
+    }
+}
@@ -0,0 +1,281 @@
+// A map with `Ranged<MIN, MAX>` keys stored in `[V; MAX-MIN+1]`. The key `k` is
+// at the position `k-MIN`, which is always inside the array.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+use core::marker::Destruct;
+use crate::{allow_range, conversions, irang, memlayout, Assert, IsAllowed, Ranged};
+use crate::arrays::fill_by_keys;
+
+/// A map from every value of `Ranged<MIN, MAX>` to `V`, backed by the array `[V; MAX-MIN+1]`
+///
+/// Unlike the arrays, the map accepts the keys with any `MIN`. The key offset is
+/// applied internally with no bounds check. Any key range inside `MIN..=MAX` is
+/// accepted, so `r!([] x)` keys need the explicit bounds, like the array indices.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let fahrenheit = RangedMap::<-40, 125, i32>::from_fn(|c| c.i32() * 9 / 5 + 32);
+/// assert_eq!(fahrenheit[r!([-40 125] 100)], 212);
+/// assert_eq!(fahrenheit[r!(-40)], -40);
+///
+/// let celsius_warm = fahrenheit.map(|f| f > 77);
+/// assert_eq!(celsius_warm.iter().filter(|&(_, &warm)| warm).count(), 100);
+/// ```
+///
+/// The map is usable in the constant contexts as a lookup table:
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// const SQUARES: RangedMap<-2, 2, u8> = RangedMap::from_array([4, 1, 0, 1, 4]);
+/// const FOUR: u8 = *SQUARES.get(r!(-2));
+/// assert_eq!(FOUR, 4);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangedMap<const MIN: irang, const MAX: irang, V>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [V; (MAX-MIN+1) as usize]:,
+{
+    data: [V; (MAX-MIN+1) as usize],
+}
+
+/// A lookup table with `Ranged<MIN, MAX>` keys, computed at compile time
+///
+/// The same type as [`RangedMap`], built with [`RangedMap::from_const_fn`]. The
+/// lookup with a `Ranged<MIN, MAX>` key has no bounds check and never fails.
+///
+/// ```
+/// #![feature(adt_const_params, generic_const_exprs, const_trait_impl)]
+/// use ranged_integers::*;
+///
+/// const fn days(month: Ranged<1, 12>) -> u8 {
+///     match month.get() {
+///         2 => 28,
+///         4 | 6 | 9 | 11 => 30,
+///         _ => 31,
+///     }
+/// }
+/// const DAYS: RangedTable<1, 12, u8> = RangedTable::from_const_fn(days);
+/// assert_eq!(DAYS[r!([1 12] 2)], 28);
+/// assert_eq!(DAYS.iter().map(|(_, &d)| u32::from(d)).sum::<u32>(), 365);
+/// ```
+pub type RangedTable<const MIN: irang, const MAX: irang, T> = RangedMap<MIN, MAX, T>;
+
+impl<const MIN: irang, const MAX: irang, V> RangedMap<MIN, MAX, V>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [V; (MAX-MIN+1) as usize]:,
+{
+    /// Create the map from the values in the key order
+    #[must_use]
+    pub const fn from_array(data: [V; (MAX-MIN+1) as usize]) -> Self {
+        Self { data }
+    }
+
+    /// Create the map calling `f` for every key in increasing order
+    #[must_use]
+    pub fn from_fn(mut f: impl FnMut(Ranged<MIN, MAX>) -> V) -> Self {
+        Self { data: core::array::from_fn(|i| f(unsafe{Ranged::unchecked_new(MIN + i as irang)})) }
+    }
+
+    /// Create the map calling `f` for every key in increasing order at compile time
+    ///
+    /// The same as [`from_fn`](Self::from_fn), but usable in the constant contexts with a
+    /// `const fn` or a const closure, which needs `#![feature(const_trait_impl)]` in the user crate.
+    /// See [`RangedTable`].
+    #[must_use]
+    pub const fn from_const_fn<F>(f: F) -> Self
+    where F: [const] FnMut(Ranged<MIN, MAX>) -> V + [const] Destruct,
+    {
+        // One value per key
+        Self { data: unsafe{fill_by_keys::<V, {(MAX-MIN+1) as usize}, MIN, MAX, F>(f)} }
+    }
+
+    /// Get the values array in the key order
+    #[must_use]
+    pub const fn as_array(&self) -> &[V; (MAX-MIN+1) as usize] {
+        &self.data
+    }
+
+    /// Get the mutable values array in the key order
+    #[must_use]
+    pub const fn as_mut_array(&mut self) -> &mut [V; (MAX-MIN+1) as usize] {
+        &mut self.data
+    }
+
+    /// Convert to the values array in the key order
+    #[must_use]
+    pub fn into_array(self) -> [V; (MAX-MIN+1) as usize] {
+        self.data
+    }
+
+    // The value of the key known to be inside `MIN..=MAX`
+    pub(crate) const fn slot(&self, key: irang) -> &V {
+        unsafe{&*self.data.as_ptr().add((key - MIN) as usize)}
+    }
+
+    // The mutable value of the key known to be inside `MIN..=MAX`
+    pub(crate) const fn slot_mut(&mut self, key: irang) -> &mut V {
+        unsafe{&mut *self.data.as_mut_ptr().add((key - MIN) as usize)}
+    }
+
+    /// Get a reference to the value of the key
+    ///
+    /// Any key range inside `MIN..=MAX` is accepted.
+    #[must_use]
+    pub const fn get<const KMIN: irang, const KMAX: irang>(&self, key: Ranged<KMIN, KMAX>) -> &V
+    where
+        Assert<{allow_range(memlayout(KMIN, KMAX))}>: IsAllowed,
+        Assert<{conversions::lesseq(MIN, KMIN)}>: IsAllowed,
+        Assert<{conversions::lesseq(KMAX, MAX)}>: IsAllowed,
+    {
+        self.slot(key.get())
+    }
+
+    /// Get a mutable reference to the value of the key
+    ///
+    /// Any key range inside `MIN..=MAX` is accepted.
+    #[must_use]
+    pub const fn get_mut<const KMIN: irang, const KMAX: irang>(&mut self, key: Ranged<KMIN, KMAX>) -> &mut V
+    where
+        Assert<{allow_range(memlayout(KMIN, KMAX))}>: IsAllowed,
+        Assert<{conversions::lesseq(MIN, KMIN)}>: IsAllowed,
+        Assert<{conversions::lesseq(KMAX, MAX)}>: IsAllowed,
+    {
+        self.slot_mut(key.get())
+    }
+
+    /// Apply `f` to every value, keeping the keys
+    pub fn map<U>(self, f: impl FnMut(V) -> U) -> RangedMap<MIN, MAX, U>
+    where [U; (MAX-MIN+1) as usize]:,
+    {
+        RangedMap { data: self.data.map(f) }
+    }
+
+    /// Iterate over the key-value pairs in the key order
+    pub fn iter(&self) -> RangedMapIter<'_, MIN, MAX, V> {
+        RangedMapIter(self.data.iter().enumerate())
+    }
+
+    /// Iterate over the key-value pairs in the key order with the mutable values
+    pub fn iter_mut(&mut self) -> RangedMapIterMut<'_, MIN, MAX, V> {
+        RangedMapIterMut(self.data.iter_mut().enumerate())
+    }
+}
+
+impl<const MIN: irang, const MAX: irang, V: Default> Default for RangedMap<MIN, MAX, V>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [V; (MAX-MIN+1) as usize]:,
+{
+    fn default() -> Self {
+        Self::from_fn(|_| V::default())
+    }
+}
+
+impl<const MIN: irang, const MAX: irang, V: core::fmt::Debug> core::fmt::Debug for RangedMap<MIN, MAX, V>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [V; (MAX-MIN+1) as usize]:,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // The key bounds are in the map type, print only the key values
+        f.debug_map().entries(self.iter().map(|(k, v)| (k.get(), v))).finish()
+    }
+}
+
+impl<const MIN: irang, const MAX: irang, V, const KMIN: irang, const KMAX: irang>
+core::ops::Index<Ranged<KMIN, KMAX>> for RangedMap<MIN, MAX, V>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [V; (MAX-MIN+1) as usize]:,
+    Assert<{allow_range(memlayout(KMIN, KMAX))}>: IsAllowed,
+    Assert<{conversions::lesseq(MIN, KMIN)}>: IsAllowed,
+    Assert<{conversions::lesseq(KMAX, MAX)}>: IsAllowed,
+{
+    type Output = V;
+    fn index(&self, key: Ranged<KMIN, KMAX>) -> &V {
+        self.get(key)
+    }
+}
+
+impl<const MIN: irang, const MAX: irang, V, const KMIN: irang, const KMAX: irang>
+core::ops::IndexMut<Ranged<KMIN, KMAX>> for RangedMap<MIN, MAX, V>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [V; (MAX-MIN+1) as usize]:,
+    Assert<{allow_range(memlayout(KMIN, KMAX))}>: IsAllowed,
+    Assert<{conversions::lesseq(MIN, KMIN)}>: IsAllowed,
+    Assert<{conversions::lesseq(KMAX, MAX)}>: IsAllowed,
+{
+    fn index_mut(&mut self, key: Ranged<KMIN, KMAX>) -> &mut V {
+        self.get_mut(key)
+    }
+}
+
+impl<'a, const MIN: irang, const MAX: irang, V> IntoIterator for &'a RangedMap<MIN, MAX, V>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [V; (MAX-MIN+1) as usize]:,
+{
+    type Item = (Ranged<MIN, MAX>, &'a V);
+    type IntoIter = RangedMapIter<'a, MIN, MAX, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, const MIN: irang, const MAX: irang, V> IntoIterator for &'a mut RangedMap<MIN, MAX, V>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [V; (MAX-MIN+1) as usize]:,
+{
+    type Item = (Ranged<MIN, MAX>, &'a mut V);
+    type IntoIter = RangedMapIterMut<'a, MIN, MAX, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+macro_rules! map_iter {
+    ($name:ident $($mut:ident)?) => {
+        impl<'a, const MIN: irang, const MAX: irang, V> Iterator for $name<'a, MIN, MAX, V>
+        where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+        {
+            type Item = (Ranged<MIN, MAX>, &'a $($mut)? V);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next().map(|(i, v)| (unsafe{Ranged::unchecked_new(MIN + i as irang)}, v))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        impl<const MIN: irang, const MAX: irang, V> DoubleEndedIterator for $name<'_, MIN, MAX, V>
+        where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+        {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.0.next_back().map(|(i, v)| (unsafe{Ranged::unchecked_new(MIN + i as irang)}, v))
+            }
+        }
+
+        impl<const MIN: irang, const MAX: irang, V> ExactSizeIterator for $name<'_, MIN, MAX, V>
+        where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+        {}
+    };
+}
+
+/// An iterator over the key-value pairs of [`RangedMap`]
+pub struct RangedMapIter<'a, const MIN: irang, const MAX: irang, V>(core::iter::Enumerate<core::slice::Iter<'a, V>>)
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed;
+
+/// An iterator over the key-value pairs of [`RangedMap`] with the mutable values
+pub struct RangedMapIterMut<'a, const MIN: irang, const MAX: irang, V>(core::iter::Enumerate<core::slice::IterMut<'a, V>>)
+where Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed;
+
+map_iter!(RangedMapIter);
+map_iter!(RangedMapIterMut mut);
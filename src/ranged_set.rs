@@ -0,0 +1,427 @@
+// A bitset over `MIN..=MAX` stored in the minimal `[u64; K]`. The value `v` is the bit
+// `(v-MIN) % 64` of the word `(v-MIN) / 64`. The bits beyond `MAX` are always zero.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign};
+use crate::{allow_range, conversions, irang, memlayout, Assert, IsAllowed, Ranged};
+
+#[must_use]
+#[doc(hidden)]
+pub const fn set_words(min: irang, max: irang) -> usize {
+    ((max - min) / 64 + 1) as usize
+}
+
+/// A set of `Ranged<MIN, MAX>` values, stored as a bitset in the minimal `[u64; K]`
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let mut candidates = RangedSet::<1, 9>::full();
+/// candidates.remove(r!(4));
+/// candidates.remove(r!(7));
+/// assert!(!candidates.contains(r!(4)));
+/// assert_eq!(candidates.len(), 7);
+///
+/// let odd: RangedSet<1, 9> = r!(1..=9).into_iter().filter(|x| x.i32() % 2 == 1).collect();
+/// assert_eq!(format!("{:?}", candidates & odd), "{1, 3, 5, 9}");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RangedSet<const MIN: irang, const MAX: irang>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    words: [u64; set_words(MIN, MAX)],
+}
+
+impl<const MIN: irang, const MAX: irang> RangedSet<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    // The word index and the bit mask of the value
+    const fn position(v: irang) -> (usize, u64) {
+        let offset = (v - MIN) as usize;
+        (offset / 64, 1 << (offset % 64))
+    }
+
+    // The used bits of the last word
+    const fn last_mask() -> u64 {
+        let bits = ((MAX - MIN) % 64 + 1) as u32;
+        u64::MAX >> (64 - bits)
+    }
+
+    // Add the value known to be inside `MIN..=MAX`
+    pub(crate) const fn insert_value(&mut self, v: irang) -> bool {
+        let (w, mask) = Self::position(v);
+        let new = self.words[w] & mask == 0;
+        self.words[w] |= mask;
+        new
+    }
+
+    // Remove the value known to be inside `MIN..=MAX`
+    pub(crate) const fn remove_value(&mut self, v: irang) -> bool {
+        let (w, mask) = Self::position(v);
+        let present = self.words[w] & mask != 0;
+        self.words[w] &= !mask;
+        present
+    }
+
+    // The least value of the set not less than `v`, which is inside `MIN..=MAX`
+    #[cfg(feature = "alloc")]  // Used by `BucketQueue`
+    pub(crate) const fn first_from(&self, v: irang) -> Option<irang> {
+        let (mut i, mask) = Self::position(v);
+        // The bits of the values below `v` are cleared in the first word
+        let mut w = self.words[i] & !(mask - 1);
+        loop {
+            if w != 0 {
+                return Some(MIN + (i * 64) as irang + w.trailing_zeros() as irang);
+            }
+            i += 1;
+            if i == set_words(MIN, MAX) {
+                return None;
+            }
+            w = self.words[i];
+        }
+    }
+
+    // The greatest value of the set not greater than `v`, which is inside `MIN..=MAX`
+    #[cfg(feature = "alloc")]  // Used by `BucketQueue`
+    pub(crate) const fn last_from(&self, v: irang) -> Option<irang> {
+        let (mut i, mask) = Self::position(v);
+        // The bits of the values above `v` are cleared in the first word
+        let mut w = self.words[i] & (mask | (mask - 1));
+        loop {
+            if w != 0 {
+                return Some(MIN + (i * 64) as irang + 63 - w.leading_zeros() as irang);
+            }
+            if i == 0 {
+                return None;
+            }
+            i -= 1;
+            w = self.words[i];
+        }
+    }
+
+    /// Create an empty set
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { words: [0; set_words(MIN, MAX)] }
+    }
+
+    /// Create a set containing every value of `MIN..=MAX`
+    #[must_use]
+    pub const fn full() -> Self {
+        let mut words = [u64::MAX; set_words(MIN, MAX)];
+        words[set_words(MIN, MAX) - 1] = Self::last_mask();
+        Self { words }
+    }
+
+    /// Add the value, return `true` if it was not in the set
+    pub const fn insert<const VMIN: irang, const VMAX: irang>(&mut self, v: Ranged<VMIN, VMAX>) -> bool
+    where
+        Assert<{allow_range(memlayout(VMIN, VMAX))}>: IsAllowed,
+        Assert<{conversions::lesseq(MIN, VMIN)}>: IsAllowed,
+        Assert<{conversions::lesseq(VMAX, MAX)}>: IsAllowed,
+    {
+        self.insert_value(v.get())
+    }
+
+    /// Remove the value, return `true` if it was in the set
+    pub const fn remove<const VMIN: irang, const VMAX: irang>(&mut self, v: Ranged<VMIN, VMAX>) -> bool
+    where
+        Assert<{allow_range(memlayout(VMIN, VMAX))}>: IsAllowed,
+        Assert<{conversions::lesseq(MIN, VMIN)}>: IsAllowed,
+        Assert<{conversions::lesseq(VMAX, MAX)}>: IsAllowed,
+    {
+        self.remove_value(v.get())
+    }
+
+    /// Check if the value is in the set
+    #[must_use]
+    pub const fn contains<const VMIN: irang, const VMAX: irang>(&self, v: Ranged<VMIN, VMAX>) -> bool
+    where
+        Assert<{allow_range(memlayout(VMIN, VMAX))}>: IsAllowed,
+        Assert<{conversions::lesseq(MIN, VMIN)}>: IsAllowed,
+        Assert<{conversions::lesseq(VMAX, MAX)}>: IsAllowed,
+    {
+        let (w, mask) = Self::position(v.get());
+        self.words[w] & mask != 0
+    }
+
+    /// The number of values in the set
+    #[must_use]
+    pub const fn len(&self) -> Ranged<0, {MAX-MIN+1}>
+    where Assert<{allow_range(memlayout(0, MAX-MIN+1))}>: IsAllowed,
+    {
+        let mut n = 0;
+        let mut i = 0;
+        while i < set_words(MIN, MAX) {
+            n += self.words[i].count_ones() as irang;
+            i += 1;
+        }
+        unsafe{Ranged::unchecked_new(n)}
+    }
+
+    /// Check if the set has no values
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        let mut i = 0;
+        while i < set_words(MIN, MAX) {
+            if self.words[i] != 0 {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// The smallest value of the set, or `None` if the set is empty
+    #[must_use]
+    pub const fn first(&self) -> Option<Ranged<MIN, MAX>> {
+        let mut i = 0;
+        while i < set_words(MIN, MAX) {
+            let w = self.words[i];
+            if w != 0 {
+                return Some(unsafe{Ranged::unchecked_new(MIN + (i * 64) as irang + w.trailing_zeros() as irang)});
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// The greatest value of the set, or `None` if the set is empty
+    #[must_use]
+    pub const fn last(&self) -> Option<Ranged<MIN, MAX>> {
+        let mut i = set_words(MIN, MAX);
+        while i > 0 {
+            i -= 1;
+            let w = self.words[i];
+            if w != 0 {
+                return Some(unsafe{Ranged::unchecked_new(MIN + (i * 64) as irang + 63 - w.leading_zeros() as irang)});
+            }
+        }
+        None
+    }
+
+    /// Remove all the values
+    pub const fn clear(&mut self) {
+        self.words = [0; set_words(MIN, MAX)];
+    }
+
+    /// Iterate over the values in increasing order
+    #[must_use]
+    pub const fn iter(&self) -> RangedSetIter<MIN, MAX> {
+        RangedSetIter { set: *self, word: 0 }
+    }
+
+    // Combine the words of two sets
+    fn zip_with(self, other: Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let mut out = self;
+        for (a, b) in out.words.iter_mut().zip(other.words) {
+            *a = f(*a, b);
+        }
+        out
+    }
+
+    /// The values in `self` or in `other`
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// The values in both `self` and `other`
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// The values in `self` but not in `other`
+    #[must_use]
+    pub fn difference(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    /// The values in exactly one of `self` and `other`
+    #[must_use]
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    /// The values of `MIN..=MAX` not in `self`
+    #[must_use]
+    pub fn complement(self) -> Self {
+        self.zip_with(Self::full(), |a, b| !a & b)
+    }
+
+    /// Check if every value of `self` is in `other`
+    #[must_use]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(*other).is_empty()
+    }
+
+    /// Check if `self` and `other` have no common values
+    #[must_use]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(*other).is_empty()
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> Default for RangedSet<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> core::fmt::Debug for RangedSet<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter().map(Ranged::get)).finish()
+    }
+}
+
+macro_rules! set_operator {
+    ($($tr:ident $method:ident $tr_assign:ident $method_assign:ident $func:ident)+) => {
+        $(
+            impl<const MIN: irang, const MAX: irang> $tr for RangedSet<MIN, MAX>
+            where
+                Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+                [u64; set_words(MIN, MAX)]:,
+            {
+                type Output = Self;
+                fn $method(self, rhs: Self) -> Self {
+                    self.$func(rhs)
+                }
+            }
+
+            impl<const MIN: irang, const MAX: irang> $tr_assign for RangedSet<MIN, MAX>
+            where
+                Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+                [u64; set_words(MIN, MAX)]:,
+            {
+                fn $method_assign(&mut self, rhs: Self) {
+                    *self = self.$func(rhs);
+                }
+            }
+        )+
+    };
+}
+
+set_operator! {
+    BitOr bitor BitOrAssign bitor_assign union
+    BitAnd bitand BitAndAssign bitand_assign intersection
+    Sub sub SubAssign sub_assign difference
+    BitXor bitxor BitXorAssign bitxor_assign symmetric_difference
+}
+
+impl<const MIN: irang, const MAX: irang> Not for RangedSet<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    type Output = Self;
+    fn not(self) -> Self {
+        self.complement()
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> FromIterator<Ranged<MIN, MAX>> for RangedSet<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    fn from_iter<I: IntoIterator<Item = Ranged<MIN, MAX>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> Extend<Ranged<MIN, MAX>> for RangedSet<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    fn extend<I: IntoIterator<Item = Ranged<MIN, MAX>>>(&mut self, iter: I) {
+        for v in iter {
+            self.insert_value(v.get());
+        }
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> IntoIterator for RangedSet<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    type Item = Ranged<MIN, MAX>;
+    type IntoIter = RangedSetIter<MIN, MAX>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> IntoIterator for &RangedSet<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    type Item = Ranged<MIN, MAX>;
+    type IntoIter = RangedSetIter<MIN, MAX>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the values of [`RangedSet`] in increasing order
+///
+/// The iterator owns a copy of the set and clears the bits it yields.
+#[derive(Clone)]
+pub struct RangedSetIter<const MIN: irang, const MAX: irang>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    set: RangedSet<MIN, MAX>,
+    word: usize,
+}
+
+impl<const MIN: irang, const MAX: irang> Iterator for RangedSetIter<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{
+    type Item = Ranged<MIN, MAX>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.word < set_words(MIN, MAX) {
+            let w = &mut self.set.words[self.word];
+            if *w != 0 {
+                let bit = w.trailing_zeros();
+                *w &= *w - 1;
+                return Some(unsafe{Ranged::unchecked_new(MIN + (self.word * 64) as irang + irang::from(bit))});
+            }
+            self.word += 1;
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.set.words[self.word..].iter().map(|w| w.count_ones() as usize).sum();
+        (n, Some(n))
+    }
+}
+
+impl<const MIN: irang, const MAX: irang> ExactSizeIterator for RangedSetIter<MIN, MAX>
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+    [u64; set_words(MIN, MAX)]:,
+{}
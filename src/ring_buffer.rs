@@ -0,0 +1,393 @@
+// A ring buffer stored inline in `[MaybeUninit<T>; N]`. The head is `Ranged<0, N-1>` and the
+// length is `Ranged<0, N>`; the logical element `i` is in the slot `(head + i) mod N`, which
+// is always a valid `Ranged<0, N-1>` index. The `len` slots starting at `head` are initialized.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+#![allow(clippy::cast_sign_loss)]
+
+use core::mem::MaybeUninit;
+use crate::{allow_range, conversions, irang, memlayout, Assert, IsAllowed, Ranged};
+
+/// A fixed-capacity ring buffer with the `Ranged` head and length
+///
+/// The head, the tail and the logical indices are `Ranged<0, {N-1}>`, so the
+/// wrap-around always gives a valid slot of the storage array and the storage is
+/// accessed with no bounds check. The length is `Ranged<0, N>`.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let mut buf = RingBuffer::<u32, 3>::new();
+/// buf.push_back(1).unwrap();
+/// buf.push_back(2).unwrap();
+/// buf.push_back(3).unwrap();
+/// assert!(buf.push_back(4).is_err());
+///
+/// // Keep the last 3 values
+/// assert_eq!(buf.push_overwrite(4), Some(1));
+/// assert_eq!(buf[r!([0 2] 0)], 2);
+///
+/// let len: Ranged<0, 3> = buf.len();
+/// assert_eq!(len, 3);
+/// assert_eq!(buf.iter().copied().collect::<Vec<_>>(), [2, 3, 4]);
+/// ```
+pub struct RingBuffer<T, const N: usize>
+where
+    // The normalization bound, see the header of arrays.rs
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    // The storage indexing with the slot numbers
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    data: [MaybeUninit<T>; N],
+    head: Ranged<0, {N as irang - 1}>,
+    len: Ranged<0, {N as irang}>,
+}
+
+impl<T, const N: usize> RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    /// Create an empty buffer
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            data: [const { MaybeUninit::uninit() }; N],
+            head: unsafe{Ranged::unchecked_new(0)},
+            len: unsafe{Ranged::unchecked_new(0)},
+        }
+    }
+
+    // The slot of the logical index `i < N`
+    const fn slot(&self, i: usize) -> Ranged<0, {N as irang - 1}> {
+        let pos = self.head.get() as usize + i;
+        let pos = if pos >= N {pos - N} else {pos};
+        unsafe{Ranged::unchecked_new(pos as irang)}
+    }
+
+    /// The number of elements in the buffer
+    #[must_use]
+    pub const fn len(&self) -> Ranged<0, {N as irang}> {
+        self.len
+    }
+
+    /// The maximum number of elements in the buffer
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Check if the buffer has no elements
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len.get() == 0
+    }
+
+    /// Check if the buffer has `N` elements
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len.get() as usize == N
+    }
+
+    /// The slot of the first element
+    #[must_use]
+    pub const fn head(&self) -> Ranged<0, {N as irang - 1}> {
+        self.head
+    }
+
+    /// The slot after the last element, where the next `push_back` writes
+    #[must_use]
+    pub const fn tail(&self) -> Ranged<0, {N as irang - 1}> {
+        // The length is N at most, which wraps to the head
+        self.slot(self.len.get() as usize)
+    }
+
+    /// Append an element to the back, or give the element back if the buffer is full
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the buffer is full.
+    pub fn push_back(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        let tail = self.tail();
+        self.data[tail].write(value);
+        self.len = unsafe{Ranged::unchecked_new(self.len.get() + 1)};
+        Ok(())
+    }
+
+    /// Prepend an element to the front, or give the element back if the buffer is full
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err(value)` if the buffer is full.
+    pub fn push_front(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.head = self.slot(N - 1);
+        self.data[self.head].write(value);
+        self.len = unsafe{Ranged::unchecked_new(self.len.get() + 1)};
+        Ok(())
+    }
+
+    /// Append an element to the back, removing and returning the front element if the buffer is full
+    pub fn push_overwrite(&mut self, value: T) -> Option<T> {
+        let evicted = if self.is_full() {self.pop_front()} else {None};
+        // There is a free slot now
+        let _ = self.push_back(value);
+        evicted
+    }
+
+    /// Remove the front element, or return `None` if the buffer is empty
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let value = unsafe{self.data[self.head].assume_init_read()};
+        self.head = self.slot(1);
+        self.len = unsafe{Ranged::unchecked_new(self.len.get() - 1)};
+        Some(value)
+    }
+
+    /// Remove the back element, or return `None` if the buffer is empty
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len = unsafe{Ranged::unchecked_new(self.len.get() - 1)};
+        let last = self.tail();
+        Some(unsafe{self.data[last].assume_init_read()})
+    }
+
+    /// Get a reference to the element at the logical index, or `None` if the index is not less than the length
+    #[must_use]
+    pub fn get(&self, i: Ranged<0, {N as irang - 1}>) -> Option<&T> {
+        if i.get() < self.len.get() {
+            Some(unsafe{self.data[self.slot(i.get() as usize)].assume_init_ref()})
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the element at the logical index, or `None` if the index is not less than the length
+    #[must_use]
+    pub fn get_mut(&mut self, i: Ranged<0, {N as irang - 1}>) -> Option<&mut T> {
+        if i.get() < self.len.get() {
+            let slot = self.slot(i.get() as usize);
+            Some(unsafe{self.data[slot].assume_init_mut()})
+        } else {
+            None
+        }
+    }
+
+    /// The front element, or `None` if the buffer is empty
+    #[must_use]
+    pub fn front(&self) -> Option<&T> {
+        self.get(unsafe{Ranged::unchecked_new(0)})
+    }
+
+    /// The back element, or `None` if the buffer is empty
+    #[must_use]
+    pub fn back(&self) -> Option<&T> {
+        let len = self.len.get();
+        if len == 0 {
+            return None;
+        }
+        self.get(unsafe{Ranged::unchecked_new(len - 1)})
+    }
+
+    /// Remove all the elements
+    pub fn clear(&mut self) {
+        while self.pop_back().is_some() {}
+        self.head = unsafe{Ranged::unchecked_new(0)};
+    }
+
+    /// Iterate over the elements from the front to the back
+    #[must_use]
+    pub const fn iter(&self) -> RingBufferIter<'_, T, N> {
+        RingBufferIter { buf: self, front: 0, back: self.len.get() as usize }
+    }
+}
+
+impl<T, const N: usize> Drop for RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    fn clone(&self) -> Self {
+        let mut out = Self::new();
+        for x in self {
+            // The same capacity, never full before all the elements are cloned
+            let _ = out.push_back(x.clone());
+        }
+        out
+    }
+}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{}
+
+impl<T, const N: usize> core::ops::Index<Ranged<0, {N as irang - 1}>> for RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    type Output = T;
+    fn index(&self, i: Ranged<0, {N as irang - 1}>) -> &T {
+        let len = self.len.get();
+        self.get(i).unwrap_or_else(|| panic!("index out of bounds: the len is {len} but the index is {i}"))
+    }
+}
+
+impl<T, const N: usize> core::ops::IndexMut<Ranged<0, {N as irang - 1}>> for RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    fn index_mut(&mut self, i: Ranged<0, {N as irang - 1}>) -> &mut T {
+        let len = self.len.get();
+        self.get_mut(i).unwrap_or_else(|| panic!("index out of bounds: the len is {len} but the index is {i}"))
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a RingBuffer<T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    type Item = &'a T;
+    type IntoIter = RingBufferIter<'a, T, N>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of [`RingBuffer`] from the front to the back
+pub struct RingBufferIter<'a, T, const N: usize>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    buf: &'a RingBuffer<T, N>,
+    // The remaining logical indices
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for RingBufferIter<'a, T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.front == self.back {
+            return None;
+        }
+        let slot = self.buf.slot(self.front);
+        self.front += 1;
+        Some(unsafe{self.buf.data[slot].assume_init_ref()})
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for RingBufferIter<'_, T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        let slot = self.buf.slot(self.back);
+        Some(unsafe{self.buf.data[slot].assume_init_ref()})
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for RingBufferIter<'_, T, N>
+where
+    Assert<{allow_range(memlayout(0, N as irang))}>: IsAllowed,
+    Assert<{allow_range(memlayout(0, N as irang - 1))}>: IsAllowed,
+    Assert<{conversions::converter_checkers::usize(0, N as irang - 1)}>: IsAllowed,
+    Assert<{conversions::lessthan(N as irang - 1, N as i128)}>: IsAllowed,
+{}
@@ -0,0 +1,165 @@
+// Sorting by Ranged keys. The key `k` is sorted as the offset `k-MIN`, which is in `0..=MAX-MIN`.
+// The offsets fitting one byte are sorted with a single counting pass; the wider ones are
+// sorted with LSD radix sort, one stable counting pass per byte of `MAX-MIN`. The radix sort
+// keeps the offsets in the smallest unsigned type fitting `MAX-MIN` and needs the `alloc`
+// feature for its buffers; the counting sort of `sort_ranged` allocates nothing.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+#[cfg(feature = "alloc")] use core::mem::MaybeUninit;
+use crate::{allow_range, irang, memlayout, Assert, IsAllowed, Ranged};
+
+// The digit size of the radix sort, also the cardinality limit of the counting sort
+const DIGIT_BITS: u32 = 8;
+const BUCKETS: usize = 1 << DIGIT_BITS;
+
+// The number of radix sort passes for the offsets `0..=span`
+#[cfg(feature = "alloc")]
+const fn radix_passes(span: irang) -> u32 {
+    (u128::BITS - (span as u128).leading_zeros()).div_ceil(DIGIT_BITS)
+}
+
+// The key offset stored by the radix sort
+#[cfg(feature = "alloc")]
+trait Offset: Copy {
+    fn from_irang(offset: irang) -> Self;
+    fn digit(self, shift: u32) -> usize;
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! offset_impl {
+    ($($t:ty)+) => {
+        $(
+            impl Offset for $t {
+                fn from_irang(offset: irang) -> Self {
+                    offset as $t
+                }
+                fn digit(self, shift: u32) -> usize {
+                    (self >> shift) as usize & (BUCKETS - 1)
+                }
+            }
+        )+
+    };
+}
+
+#[cfg(feature = "alloc")]
+offset_impl! {u8 u16 u32 u64 u128}
+
+/// Sort the slice of `Ranged` values in increasing order
+///
+/// The algorithm is chosen at compile time from the range size: counting sort
+/// for at most 256 values, with no extra memory, and LSD radix sort with a buffer
+/// of the slice length otherwise. Takes `O(n)` time for the given type. Without
+/// the `alloc` feature, the ranges of more than 256 values are sorted with
+/// [`sort_unstable`](slice::sort_unstable).
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// let mut dice = [r!([1 6] 4), r!([] 1), r!([] 6), r!([] 1), r!([] 3)];
+/// sort_ranged(&mut dice);
+/// assert_eq!(dice.map(Ranged::i32), [1, 1, 3, 4, 6]);
+/// ```
+pub fn sort_ranged<const MIN: irang, const MAX: irang>(slice: &mut [Ranged<MIN, MAX>])
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    if const { MAX - MIN < BUCKETS as irang } {
+        let mut counts = [0_usize; BUCKETS];
+        for x in slice.iter() {
+            counts[(x.get() - MIN) as usize] += 1;
+        }
+        let mut pos = 0;
+        for (offset, &count) in counts.iter().enumerate() {
+            let value = unsafe{Ranged::unchecked_new(MIN + offset as irang)};
+            slice[pos..pos + count].fill(value);
+            pos += count;
+        }
+    } else {
+        #[cfg(feature = "alloc")]
+        sort_by_ranged_key(slice, |x| *x);
+        #[cfg(not(feature = "alloc"))]
+        slice.sort_unstable();
+    }
+}
+
+/// Stably sort the slice by the `Ranged` key
+///
+/// The key is called once per element. The algorithm is chosen at compile time
+/// from the key range size: counting sort for at most 256 keys, LSD radix sort
+/// otherwise. Both use a buffer of the slice length and take `O(n)` time for the
+/// given key type. Available with the `alloc` feature.
+///
+/// ```
+/// # #![feature(adt_const_params, generic_const_exprs)] use ranged_integers::*;
+/// # #[cfg(not(feature = "alloc"))] fn main() {}
+/// # #[cfg(feature = "alloc")] fn main() {
+/// let mut people = [("Ann", 31), ("Bob", 25), ("Cid", 31), ("Dan", 25)];
+/// sort_by_ranged_key(&mut people, |&(_, age)| Ranged::<0, 150>::new(age).unwrap());
+/// assert_eq!(people, [("Bob", 25), ("Dan", 25), ("Ann", 31), ("Cid", 31)]);
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn sort_by_ranged_key<T, const MIN: irang, const MAX: irang>(slice: &mut [T], key: impl Fn(&T) -> Ranged<MIN, MAX>)
+where
+    Assert<{allow_range(memlayout(MIN, MAX))}>: IsAllowed,
+{
+    let passes = const { radix_passes(MAX - MIN) };
+    match passes {
+        0 => {}
+        1 => radix_sort::<T, u8>(slice, |x| key(x).get() - MIN, passes),
+        2 => radix_sort::<T, u16>(slice, |x| key(x).get() - MIN, passes),
+        3..=4 => radix_sort::<T, u32>(slice, |x| key(x).get() - MIN, passes),
+        5..=8 => radix_sort::<T, u64>(slice, |x| key(x).get() - MIN, passes),
+        _ => radix_sort::<T, u128>(slice, |x| key(x).get() - MIN, passes),
+    }
+}
+
+// LSD radix sort by the key offsets of the type `K` fitting all the offsets
+#[cfg(feature = "alloc")]
+fn radix_sort<T, K: Offset>(slice: &mut [T], offset: impl Fn(&T) -> irang, passes: u32) {
+    let n = slice.len();
+    if n < 2 {
+        return;
+    }
+
+    // All the keys are taken before moving the elements, so a panicking key leaves the slice intact
+    let mut keys: Vec<K> = slice.iter().map(|x| K::from_irang(offset(x))).collect();
+    let mut keys_buf = keys.clone();
+    let mut buf: Vec<MaybeUninit<T>> = Vec::with_capacity(n);
+    let base = slice.as_mut_ptr();
+    let mut src = base;
+    let mut dst = buf.as_mut_ptr().cast::<T>();
+
+    // A stable counting pass per digit; the narrow keys take a single pass, which is the counting sort
+    for pass in 0..passes {
+        let shift = pass * DIGIT_BITS;
+        let digit = |k: K| k.digit(shift);
+
+        let mut pos = [0_usize; BUCKETS];
+        for &k in &keys {
+            pos[digit(k)] += 1;
+        }
+        let mut start = 0;
+        for p in &mut pos {
+            let count = *p;
+            *p = start;
+            start += count;
+        }
+        for (i, &k) in keys.iter().enumerate() {
+            let d = &mut pos[digit(k)];
+            // The positions are a permutation of `0..n`, every element is moved exactly once
+            unsafe{core::ptr::copy_nonoverlapping(src.add(i), dst.add(*d), 1)};
+            keys_buf[*d] = k;
+            *d += 1;
+        }
+        core::mem::swap(&mut src, &mut dst);
+        core::mem::swap(&mut keys, &mut keys_buf);
+    }
+
+    // After an odd number of passes the elements are in the buffer
+    if src != base {
+        unsafe{core::ptr::copy_nonoverlapping(src, base, n)};
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::missing_const_for_fn)]
 #![allow(clippy::cognitive_complexity)]
+#![allow(clippy::manual_assert_eq)]
 
 use std::prelude::v1::*;
 
@@ -76,6 +77,43 @@ fn print_val() {
     assert_eq!(format!("{x}"), "-40");
 }
 
+#[test]
+fn print_radix() {
+    let x = r!([0 255] 171);
+    assert_eq!(format!("{x:x} {x:X} {x:o} {x:b}"), "ab AB 253 10101011");
+    assert_eq!(format!("{x:#06x} {x:e} {x:E}"), "0x00ab 1.71e2 1.71E2");
+
+    // Negative values are printed like the storage primitive
+    let x = r!([-128 127] -1);
+    assert_eq!(format!("{x:x} {x:b}"), "ff 11111111");
+    let x = r!([-1 200] -1);
+    assert_eq!(format!("{x:X}"), "FFFF");
+    assert_eq!(format!("{:x}", r!(-1)), "ff");
+    assert_eq!(format!("{:x}", r!(70000)), "11170");
+
+    assert_eq!(format!("{}", r!([0 999] 7).padded()), "007");
+    assert_eq!(format!("{}", r!([0 999] 999).padded()), "999");
+    assert_eq!(format!("{}", r!([-99 9] -5).padded()), "-05");
+    assert_eq!(format!("{}", r!([-99 9] 5).padded()), "05");
+    assert_eq!(format!("{}", r!(5).padded()), "5");
+    assert_eq!(format!("{:x}", r!([0 0xFFFF] 0xAB).padded()), "00ab");
+    assert_eq!(format!("{:X}", r!([0 0x1000] 0xAB).padded()), "00AB");
+    assert_eq!(format!("{:o}", r!([0 64] 8).padded()), "010");
+    assert_eq!(format!("{:b}", r!([0 9] 2).padded()), "0010");
+    assert_eq!(format!("{:x}", r!([-100 100] 10).padded()), "0a");
+    assert_eq!(format!("{:o}", r!([-100 100] -1).padded()), "377");
+
+    // The formatter flags apply to the padded digits
+    assert_eq!(format!("{:>6}", r!([0 999] 7).padded()), "   007");
+    assert_eq!(format!("{:*<6}", r!([-99 9] -5).padded()), "-05***");
+    assert_eq!(format!("{:^7}", r!([0 999] 42).padded()), "  042  ");
+    assert_eq!(format!("{:+}", r!([-99 9] 5).padded()), "+05");
+    assert_eq!(format!("{:05}", r!([-99 9] -5).padded()), "-0005");
+    assert_eq!(format!("{:#x}", r!([0 0xFFFF] 0xAB).padded()), "0x00ab");
+    assert_eq!(format!("{:#010b}", r!([0 9] 2).padded()), "0b00000010");
+    assert_eq!(format!("{:>6o}", r!([0 64] 8).padded()), "   010");
+}
+
 #[test]
 fn ranged_macro() {
     let x = r! {[0 4] 2};
@@ -393,6 +431,119 @@ fn convert() {
     assert_eq!(r!(10), x);
 }
 
+#[test]
+fn convert_generic() {
+    fn sum_as<T: TryFrom<i128> + core::iter::Sum>(xs: &[Ranged<0, 1000>]) -> Option<T> {
+        xs.iter().map(|x| x.try_to().ok()).sum()
+    }
+
+    let x = r!([-10 300] 20);
+    assert_eq!(x.get(), 20);
+    assert_eq!(x.try_to::<u8>(), Ok(20));
+    assert_eq!(x.try_to::<i8>(), Ok(20));
+    assert_eq!(x.try_to::<u128>(), Ok(20));
+    assert!(r!([-10 300] 300).try_to::<u8>().is_err());
+    assert!(r!([-10 300] 300).try_to::<i8>().is_err());
+    assert!(r!([-10 300] -1).try_to::<u64>().is_err());
+    assert_eq!(r!([-10 300] -1).try_to::<i64>(), Ok(-1));
+
+    assert_eq!(x.to::<i16>(), 20);
+    assert_eq!(x.to::<i128>(), 20);
+    assert!((x.to::<f32>() - 20.0).abs() < f32::EPSILON);
+
+    assert_eq!(sum_as::<u8>(&[r!([] 100), r!([] 100)]), Some(200));
+    assert_eq!(sum_as::<u8>(&[r!([] 100), r!([] 300)]), None);
+}
+
+#[test]
+fn convert_special() {
+    use core::num::{NonZeroI8, NonZeroU8, NonZeroU16, NonZeroUsize};
+
+    let t: Ranged<0, 1> = true.as_ranged();
+    assert_eq!(t, r!(1));
+    assert_eq!(false.as_ranged(), r!(0));
+    assert!(bool::from(t));
+    assert!(!bool::from(r!(0)));
+
+    let c: Ranged<0, 0x0010_FFFF> = 'A'.as_ranged();
+    assert_eq!(c, 65);
+    assert_eq!(char::try_from(c), Ok('A'));
+    assert!(char::try_from(Ranged::<0, 0x0010_FFFF>::new(0xD800).unwrap()).is_err());
+    assert_eq!(char::from(r!([0 127] 97)), 'a');
+    assert_eq!(char::from(r!([0xE000 0x0010_FFFF] 0x0010_FFFF)), '\u{10FFFF}');
+
+    let n: Ranged<1, 255> = NonZeroU8::new(42).unwrap().as_ranged();
+    assert_eq!(n, r!(42));
+    assert_eq!(NonZeroU8::from(n).get(), 42);
+    assert_eq!(NonZeroU16::from(r!([1 6] 5)).get(), 5);
+    assert_eq!(NonZeroUsize::from(r!([5 1000] 500)).get(), 500);
+
+    let n: Ranged<-128, 127> = NonZeroI8::new(-5).unwrap().as_ranged();
+    assert_eq!(n, r!(-5));
+    assert_eq!(NonZeroI8::try_from(n).unwrap().get(), -5);
+    assert!(NonZeroI8::try_from(r!([-128 127] 0)).is_err());
+    assert_eq!(NonZeroI8::from(r!([-10 -1] -3)).get(), -3);
+}
+
+#[test]
+fn convert_float() {
+    type R = Ranged<-10, 10>;
+    const C: Option<Ranged<0, 100>> = Ranged::from_f64_round(99.9, RoundingMode::Nearest);
+
+    assert!((f32::from(r!([0 1_000_000] 123_456)) - 123_456.0).abs() < f32::EPSILON);
+    assert!((f32::from(r!([-16_777_216 16_777_216] -16_777_216)) + 16_777_216.0).abs() < f32::EPSILON);
+    assert!((f64::from(r!([-9_007_199_254_740_992 0] -5)) + 5.0).abs() < f64::EPSILON);
+
+    let round = |x, mode| R::from_f64_round(x, mode).map(Ranged::i8);
+    assert_eq!(round(2.5, RoundingMode::Nearest), Some(3));
+    assert_eq!(round(2.4999, RoundingMode::Nearest), Some(2));
+    assert_eq!(round(0.499_999_999_999_999_94, RoundingMode::Nearest), Some(0));
+    assert_eq!(round(-2.5, RoundingMode::Nearest), Some(-3));
+    assert_eq!(round(-2.5, RoundingMode::Floor), Some(-3));
+    assert_eq!(round(-2.5, RoundingMode::Ceil), Some(-2));
+    assert_eq!(round(-2.5, RoundingMode::Trunc), Some(-2));
+    assert_eq!(round(2.5, RoundingMode::Floor), Some(2));
+    assert_eq!(round(2.5, RoundingMode::Ceil), Some(3));
+    assert_eq!(round(-3.0, RoundingMode::Floor), Some(-3));
+    assert_eq!(round(10.2, RoundingMode::Trunc), Some(10));
+    assert_eq!(round(10.2, RoundingMode::Ceil), None);
+    assert_eq!(round(-10.5, RoundingMode::Nearest), None);
+    assert_eq!(round(f64::NAN, RoundingMode::Nearest), None);
+    assert_eq!(round(f64::NEG_INFINITY, RoundingMode::Nearest), None);
+    assert_eq!(round(1e300, RoundingMode::Ceil), None);
+
+    let sat = |x, mode| R::from_f64_saturating(x, mode).i8();
+    assert_eq!(sat(100.0, RoundingMode::Nearest), 10);
+    assert_eq!(sat(-1e300, RoundingMode::Floor), -10);
+    assert_eq!(sat(f64::NAN, RoundingMode::Nearest), 0);
+    assert_eq!(sat(-0.7, RoundingMode::Nearest), -1);
+
+    assert_eq!(C, Some(r!([] 100)));
+}
+
+#[test]
+fn try_from_int() {
+    assert_eq!(Ranged::<1, 100>::try_from(42_u16), Ok(r!([] 42)));
+    assert_eq!(Ranged::<1, 100>::try_from(100_i128), Ok(r!([] 100)));
+    assert_eq!(Ranged::<-5, 5>::try_from(-5_i8), Ok(r!([] -5)));
+    assert_eq!(Ranged::<0, 255>::try_from(255_usize), Ok(r!([] 255)));
+
+    let err = Ranged::<1, 100>::try_from(0_u8).unwrap_err();
+    assert_eq!((err.value(), err.min(), err.max()), (0, 1, 100));
+    assert_eq!(err.kind(), OutOfRangeKind::BelowMin);
+    assert_eq!(err.to_string(), "value 0 is below the minimum 1 of the range 1..=100");
+
+    let err = Ranged::<-5, 5>::try_from(i64::MAX).unwrap_err();
+    assert_eq!(err.value(), i128::from(i64::MAX));
+    assert_eq!(err.kind(), OutOfRangeKind::AboveMax);
+
+    let err = Ranged::<-5, 5>::try_from(i128::MIN).unwrap_err();
+    assert_eq!(err.kind(), OutOfRangeKind::BelowMin);
+
+    let boxed: Box<dyn core::error::Error> = Box::new(err);
+    assert!(boxed.to_string().starts_with("value -170141183460469231731687303715884105728 is below"));
+}
+
 #[test]
 fn expand() {
     let x = r!([0 100] 20);
@@ -453,7 +604,7 @@ fn iter() {
     fibonacci[0] = 1;
     fibonacci[1] = 1;
     for i in r!(2..=9) {
-        fibonacci[i.expand()] = fibonacci[(i-r!(1)).expand()] + fibonacci[(i-r!(2)).expand()];
+        fibonacci[i] = fibonacci[i-r!(1)] + fibonacci[i-r!(2)];
     }
     assert_eq!(fibonacci, [1,1,2,3,5,8,13,21,34,55]);
 
@@ -468,6 +619,162 @@ fn iter() {
     assert_eq!(fib234, [0,5,15]);
 }
 
+#[test]
+fn iter_double_ended() {
+    let values = |it: iter::Iter<-2, 5>| it.map(Ranged::i32).collect::<Vec<_>>();
+
+    let mut it = r!(-2..=5).into_iter();
+    assert_eq!(it.len(), 8);
+    assert_eq!(it.size_hint(), (8, Some(8)));
+    it.next();
+    it.next_back();
+    assert_eq!(it.len(), 6);
+    assert_eq!(values(it.clone()), [-1, 0, 1, 2, 3, 4]);
+    assert_eq!(format!("{it:?}"), "Iter { front: -1, back: 4 }");
+
+    assert_eq!(r!(-2..=5).into_iter().rev().map(Ranged::i32).collect::<Vec<_>>(), [5, 4, 3, 2, 1, 0, -1, -2]);
+    assert_eq!(r!(0..=9).into_iter().rev().step_by(3).map(Ranged::i32).collect::<Vec<_>>(), [9, 6, 3, 0]);
+
+    // Meeting in the middle
+    let mut it = r!(0..=2).into_iter();
+    assert_eq!(it.next().unwrap(), 0);
+    assert_eq!(it.next_back().unwrap(), 2);
+    assert_eq!(it.next_back().unwrap(), 1);
+    assert!(it.next().is_none() && it.next_back().is_none());
+    assert_eq!(it.len(), 0);
+
+    let mut it = r!(0..=9).into_iter();
+    assert_eq!(it.nth(3).unwrap(), 3);
+    assert_eq!(it.nth_back(2).unwrap(), 7);
+    assert_eq!(it.len(), 3);
+    assert!(it.clone().nth(3).is_none());
+    assert!(it.nth_back(usize::MAX).is_none());
+    // Fused
+    assert!(it.next().is_none());
+
+    let it = r!(-2..=5).into_iter();
+    assert_eq!((it.clone().min().unwrap().i32(), it.clone().max().unwrap().i32(), it.last().unwrap().i32()), (-2, 5, 5));
+
+    assert_eq!(r!([0 9] 7).iter_up().map(Ranged::i32).collect::<Vec<_>>(), [7, 8, 9]);
+    assert_eq!(r!([0 9] 2).iter_down().map(Ranged::i32).collect::<Vec<_>>(), [2, 1, 0]);
+    assert_eq!(r!([0 9] 0).iter_down().len(), 1);
+    assert_eq!(r!([-5 5] 5).iter_down().len(), 11);
+    assert_eq!(r!([0 9] 5).iter_down().rev().map(Ranged::i32).collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+    assert_eq!(r!([0 9] 5).iter_down().nth(2).map(Ranged::i32), Some(3));
+    assert_eq!(r!([0 9] 5).iter_down().last().map(Ranged::i32), Some(0));
+}
+
+#[test]
+fn iter_exclusive() {
+    const N: usize = 5;
+    assert_eq!(r!(0..N as i128).into_iter().map(Ranged::i32).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+    assert_eq!(r!(-3..-1).into_iter().map(Ranged::i32).collect::<Vec<_>>(), [-3, -2]);
+    assert_eq!(r!(-1..2).into_iter().rev().map(Ranged::i32).collect::<Vec<_>>(), [1, 0, -1]);
+    assert_eq!(r!(7..8).into_iter().len(), 1);
+
+    let mut arr = [10, 11, 12, 13, 14];
+    for i in r!(0..N as i128) {
+        let i: Ranged<0, 4> = i;
+        arr[i] += 1;
+    }
+    assert_eq!(arr[r!(0..2)], [11, 12]);
+    assert_eq!(arr[r!(0..N as i128)], arr);
+    arr[r!(3..5)] = [0, 0];
+    assert_eq!(arr, [11, 12, 13, 0, 0]);
+
+    let inclusive: ConstInclusiveRange<2, 4> = r!(2..5).into();
+    assert_eq!(arr[inclusive], arr[r!(2..5)]);
+
+    // Both range kinds take the end the same way
+    assert_eq!(arr[r!(0..=N as i128 - 1)], arr[r!(0..N as i128)]);
+    assert_eq!(arr[r!(1..=N as i128 - 2)], arr[r!(1..N as i128 - 1)]);
+    assert!((0..2).all(|_| r!(0..3).into_iter().len() == r!(0..=2).into_iter().len()));
+}
+
+#[test]
+fn iter_product() {
+    let pairs = r!(0..=1).cross(r!(-1..=1));
+    assert_eq!(pairs.len(), 6);
+    assert_eq!(format!("{pairs:?}"), "Product2 { front: 0, back: 6 }");
+    let values: Vec<(i32, i32)> = pairs.clone().map(|(a, b)| (a.i32(), b.i32())).collect();
+    assert_eq!(values, [(0, -1), (0, 0), (0, 1), (1, -1), (1, 0), (1, 1)]);
+    let mut reversed: Vec<(i32, i32)> = pairs.rev().map(|(a, b)| (a.i32(), b.i32())).collect();
+    reversed.reverse();
+    assert_eq!(reversed, values);
+
+    // Matches the nested loops
+    let mut nested = Vec::new();
+    for a in r!(1..=2) {
+        for b in r!(0..=2) {
+            for c in r!(5..=6) {
+                for d in r!(-1..=0) {
+                    nested.push((a.i32(), b.i32(), c.i32(), d.i32()));
+                }
+            }
+        }
+    }
+    let quads = r!(1..=2).cross(r!(0..=2)).cross(r!(5..=6)).cross(r!(-1..=0));
+    assert_eq!(quads.len(), 24);
+    assert_eq!(quads.map(|(a, b, c, d)| (a.i32(), b.i32(), c.i32(), d.i32())).collect::<Vec<_>>(), nested);
+
+    // Meeting in the middle, skipping and extending the partially consumed product
+    let mut triples = r!(0..=2).cross(r!(0..=2)).cross(r!(0..=1));
+    assert_eq!(triples.nth(4).map(|(a, b, c)| (a.i32(), b.i32(), c.i32())), Some((0, 2, 0)));
+    assert_eq!(triples.nth_back(5).map(|(a, b, c)| (a.i32(), b.i32(), c.i32())), Some((2, 0, 0)));
+    assert_eq!(triples.len(), 7);
+    let mut pairs = r!(0..=2).cross(r!(0..=2));
+    pairs.next();
+    pairs.next_back();
+    let triples = pairs.cross(r!(0..=1));
+    assert_eq!(triples.len(), 14);
+    assert_eq!(triples.clone().next().map(|(a, b, c)| (a.i32(), b.i32(), c.i32())), Some((0, 1, 0)));
+    assert_eq!(triples.last().map(|(a, b, c)| (a.i32(), b.i32(), c.i32())), Some((2, 1, 1)));
+
+    let mut single = r!(3..=3).cross(r!(4..=4));
+    assert_eq!(single.len(), 1);
+    assert!(single.next().is_some() && single.next().is_none() && single.next_back().is_none());
+
+    let grid = [[1, 2, 3], [4, 5, 6]];
+    assert_eq!(r!(0..=1).cross(r!(0..=2)).map(|(i, j)| grid[i][j]).max(), Some(6));
+    assert_eq!(r!(0..=1).cross(r!(0..=2)).map(|(i, j)| grid[Idx2(i, j)]).sum::<i32>(), 21);
+}
+
+#[test]
+fn iter_runtime_ranges() {
+    let lo = Ranged::<0, 99>::new(3).unwrap();
+    let hi = Ranged::<0, 99>::new(7).unwrap();
+    let values = |it: &mut dyn Iterator<Item = Ranged<0, 99>>| it.map(Ranged::i32).collect::<Vec<_>>();
+
+    assert_eq!(values(&mut (lo..hi)), [3, 4, 5, 6]);
+    assert_eq!(values(&mut (lo..=hi)), [3, 4, 5, 6, 7]);
+    assert_eq!(values(&mut (lo..=hi).rev()), [7, 6, 5, 4, 3]);
+    assert_eq!((lo..=hi).size_hint(), (5, Some(5)));
+    assert_eq!((hi..lo).count(), 0);
+    assert_eq!((lo..=hi).nth(10), None);
+    assert_eq!((lo..=hi).step_by(2).map(Ranged::i32).collect::<Vec<_>>(), [3, 5, 7]);
+
+    // The full range of the type does not overflow
+    let min = Ranged::<{i64::MIN as irang}, {i64::MAX as irang}>::new(i64::MIN.into()).unwrap();
+    let max = Ranged::<{i64::MIN as irang}, {i64::MAX as irang}>::new(i64::MAX.into()).unwrap();
+    assert_eq!((max..=max).count(), 1);
+    assert_eq!((min..=max).nth_back(1).unwrap().get(), irang::from(i64::MAX) - 1);
+    assert_eq!((min..max).size_hint(), (usize::MAX, Some(usize::MAX)));
+    assert_eq!((min..=max).size_hint(), (usize::MAX, None));
+
+    assert_eq!(r!([0 9] 0).succ().unwrap(), 1);
+    assert!(r!([0 9] 9).succ().is_none());
+    assert_eq!(r!([-3 3] -2).pred().unwrap(), -3);
+    assert!(r!([-3 3] -3).pred().is_none());
+
+    let full: core::ops::RangeInclusive<Ranged<0, 99>> = ConstInclusiveRange::<0, 99>.into();
+    assert_eq!(full.clone().count(), 100);
+    assert!(ConstInclusiveRange::try_from(full.clone()).is_ok());
+    assert_eq!(ConstInclusiveRange::try_from(lo..=hi).err(), Some(lo..=hi));
+    let mut exhausted = full;
+    exhausted.by_ref().for_each(drop);
+    assert!(ConstInclusiveRange::try_from(exhausted).is_err());
+}
+
 
 #[test]
 fn fromstr() {
@@ -485,6 +792,49 @@ fn fromstr() {
 
     let x = r!(16).to_string();
     assert_eq!(x, "16");
+
+    assert_eq!("".parse::<Ranged<0, 100>>(), Err(ParseRangedError::Invalid));
+    assert_eq!("4 2".parse::<Ranged<0, 100>>(), Err(ParseRangedError::Invalid));
+    assert_eq!("-1".parse::<Ranged<0, 100>>(), Err(ParseRangedError::BelowMin { min: 0 }));
+    assert_eq!("101".parse::<Ranged<0, 100>>(), Err(ParseRangedError::AboveMax { max: 100 }));
+    let huge = "9".repeat(50);
+    assert_eq!(huge.parse::<Ranged<0, 100>>(), Err(ParseRangedError::AboveMax { max: 100 }));
+    assert_eq!(format!("-{huge}").parse::<Ranged<0, 100>>(), Err(ParseRangedError::BelowMin { min: 0 }));
+    assert_eq!(ParseRangedError::AboveMax { max: 100 }.to_string(), "number is above the maximum 100");
+}
+
+#[test]
+fn digits() {
+    for (i, b) in (b'0'..=b'9').enumerate() {
+        let d = Ranged::<0, 9>::from_ascii_digit(b).unwrap();
+        assert_eq!(d.usize(), i);
+        assert_eq!(d.to_ascii_digit(), b);
+    }
+    assert_eq!(Ranged::<0, 9>::from_ascii_digit(b'/'), None);
+    assert_eq!(Ranged::<0, 9>::from_ascii_digit(b':'), None);
+
+    let s: String = r!(0..=35).into_iter().map(|d| char::from(d.to_digit_char())).collect();
+    assert_eq!(s, "0123456789abcdefghijklmnopqrstuvwxyz");
+    for c in s.chars() {
+        let d = Ranged::<0, 35>::from_digit_char(c, 36).unwrap();
+        assert_eq!(char::from(d.to_digit_char()), c);
+        assert_eq!(Ranged::<0, 35>::from_digit_char(c.to_ascii_uppercase(), 36), Some(d));
+    }
+    assert_eq!(Ranged::<0, 35>::from_digit_char('a', 10), None);
+    assert_eq!(Ranged::<0, 35>::from_digit_char('1', 2), Some(r!([] 1)));
+    assert_eq!(Ranged::<0, 35>::from_digit_char('2', 2), None);
+    let _: Ranged<48, 122> = r!([0 35] 0).to_digit_char();
+}
+
+#[test]
+fn fromstr_radix() {
+    assert_eq!(Ranged::<0, 255>::from_str_radix("7f", 16), Ok(r!([] 127)));
+    assert_eq!(Ranged::<0, 255>::from_str_radix("FF", 16), Ok(r!([] 255)));
+    assert_eq!(Ranged::<0, 255>::from_str_radix("100", 16), Err(ParseRangedError::AboveMax { max: 255 }));
+    assert_eq!(Ranged::<-8, 7>::from_str_radix("-10", 8), Ok(r!([] -8)));
+    assert_eq!(Ranged::<0, 15>::from_str_radix("1010", 2), Ok(r!([] 10)));
+    assert_eq!(Ranged::<0, 15>::from_str_radix("102", 2), Err(ParseRangedError::Invalid));
+    assert_eq!(Ranged::<0, 35>::from_str_radix("z", 36), Ok(r!([] 35)));
 }
 
 
@@ -656,7 +1006,497 @@ fn type_constraining_comparisons() {
 #[test]
 fn test_index_slice() {
     let arr = ['a', 'b', 'c', 'd', 'e'];
-    let char = arr[r!([] 4)];
+    let char = arr[r!(4)];
     assert_eq!(char, 'e');
+
+    let mut arr = [0; 10];
+    for i in r!(2..=5) {
+        arr[i] = i.i32();
+    }
+    arr[r!([0 3] 1)] = 1;
+    assert_eq!(arr, [0, 1, 2, 3, 4, 5, 0, 0, 0, 0]);
+    let i: Ranged<7, 9> = r!([] 9);
+    arr[i] = 9;
+    assert_eq!(arr[i], 9);
+}
+
+#[test]
+fn test_window() {
+    let samples = [1, 2, 3, 4, 5, 6];
+    let sums: Vec<i32> = r!(0..=3).into_iter().map(|start| samples.window::<3>(start).iter().sum()).collect();
+    assert_eq!(sums, [6, 9, 12, 15]);
+
+    assert_eq!(samples.window::<6>(r!(0)), &samples);
+    assert_eq!(samples.window::<0>(r!([0 6] 6)), &[0; 0]);
+    assert_eq!(samples.window::<1>(r!([0 5] 5)), &[6]);
+
+    let mut buf = [0_u8; 8];
+    buf.window_mut::<2>(r!([0 6] 3)).copy_from_slice(&[7, 8]);
+    *buf.window_mut::<8>(r!(0)).last_mut().unwrap() = 1;
+    assert_eq!(buf, [0, 0, 0, 7, 8, 0, 0, 1]);
+}
+
+#[test]
+fn test_split_concat_chunks() {
+    let arr = [1, 2, 3, 4, 5, 6];
+    assert_eq!(arr.split_fixed::<0>(), (&[], &arr));
+    assert_eq!(arr.split_fixed::<4>(), (&[1, 2, 3, 4], &[5, 6]));
+    assert_eq!(arr.split_fixed::<6>(), (&arr, &[]));
+
+    let mut buf = arr;
+    let (a, b) = buf.split_fixed_mut::<3>();
+    a.swap_with_slice(b);
+    assert_eq!(buf, [4, 5, 6, 1, 2, 3]);
+
+    assert_eq!([1, 2, 3].concat_fixed([4, 5, 6]), arr);
+    assert_eq!([0_u8; 0].concat_fixed([1]), [1]);
+    let strings = [String::from("a")].concat_fixed([String::from("b"), String::from("c")]);
+    assert_eq!(strings.concat(), "abc");
+
+    assert_eq!(arr.chunks_fixed::<3>(), &[[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(arr.chunks_fixed::<1>()[r!(5)], [6]);
+    let sums: Vec<i32> = r!(0..=2).into_iter().map(|i| arr.chunk::<2>(i).iter().sum()).collect();
+    assert_eq!(sums, [3, 7, 11]);
+
+    let mut buf = arr;
+    buf.chunks_fixed_mut::<2>()[r!(0)] = [0, 0];
+    buf.chunk_mut::<3>(r!([0 1] 1))[2] = 0;
+    assert_eq!(buf, [0, 0, 3, 4, 5, 0]);
+}
+
+#[test]
+fn test_search_ranged() {
+    let arr = [5, 3, 8, 3, 8, 1];
+    let idx: Vec<i32> = arr.enumerate_ranged().map(|(i, _)| i.i32()).collect();
+    assert_eq!(idx, [0, 1, 2, 3, 4, 5]);
+    assert_eq!(arr.enumerate_ranged().len(), 6);
+    assert_eq!(arr.enumerate_ranged().next_back().map(|(i, x)| (i.get(), *x)), Some((5, 1)));
+    for (i, x) in arr.enumerate_ranged() {
+        assert_eq!(arr[i], *x);
+    }
+
+    // Cloned for the non-Clone elements, fused like the slice iterator
+    let items = [std::sync::Mutex::new(1), std::sync::Mutex::new(2)];
+    let mut it = items.enumerate_ranged();
+    assert_eq!(it.next().map(|(i, x)| (i.get(), *x.lock().unwrap())), Some((0, 1)));
+    assert_eq!(it.clone().map(|(_, x)| *x.lock().unwrap()).collect::<Vec<_>>(), [2]);
+    assert_eq!(it.size_hint(), (1, Some(1)));
+    assert!(it.nth(1).is_none() && it.next().is_none());
+    assert!(format!("{:?}", arr.enumerate_ranged()).starts_with("EnumerateRanged"));
+
+    assert_eq!(arr.position_ranged(|&x| x == 3).unwrap(), 1);
+    assert_eq!(arr.rposition_ranged(|&x| x == 3).unwrap(), 3);
+    assert!(arr.position_ranged(|&x| x > 10).is_none());
+    assert_eq!(arr.find_index(&8).unwrap(), 2);
+    assert!(arr.find_index(&0).is_none());
+
+    assert_eq!(arr.max_index().unwrap(), 4);
+    assert_eq!(arr.min_index().unwrap(), 5);
+    assert_eq!([2, 2, 2].max_index().unwrap(), 2);
+    assert_eq!([2, 2, 2].min_index().unwrap(), 0);
+
+    let mut arr = arr;
+    let i = arr.max_index().unwrap();
+    arr[i] = 0;
+    assert_eq!(arr, [5, 3, 8, 3, 0, 1]);
+}
+
+#[test]
+fn test_index_2d() {
+    let mut grid = [[0; 4]; 3];
+    for p in r!(0..=11) {
+        let idx = Idx2::<Ranged<0, 2>, Ranged<0, 3>>::unflatten(p);
+        assert_eq!(idx.flatten(), p);
+        grid[idx] = p.i32();
+    }
+    assert_eq!(grid, [[0, 1, 2, 3], [4, 5, 6, 7], [8, 9, 10, 11]]);
+    assert_eq!(grid[Idx2(r!(2), r!(0))], 8);
+    assert_eq!(grid[Idx2::from((r!([1 2] 1), r!([0 3] 3)))], 7);
+
+    let mut cube = [[[0_u8; 2]; 3]; 4];
+    cube[Idx3(r!(3), r!(2), r!(1))] = 1;
+    cube[Idx3::from((r!([0 3] 0), r!([0 1] 1), r!([1 1] 1)))] += 2;
+    assert_eq!(cube[3][2][1], 1);
+    assert_eq!(cube[Idx3(r!(0), r!(1), r!(1))], 2);
+}
+
+#[test]
+fn test_bounded_vec() {
+    let mut v = BoundedVec::<String, 3>::new();
+    assert!(v.is_empty());
+    assert_eq!(v.capacity(), 3);
+    assert_eq!(v.push("a".into()).unwrap(), 1);
+    assert_eq!(v.push("b".into()).unwrap(), 2);
+    assert_eq!(v.push("c".into()).unwrap(), 3);
+    assert!(v.is_full());
+    assert_eq!(v.push("d".into()), Err("d".into()));
+    assert_eq!(v.len(), 3);
+    v.as_mut_array().unwrap()[r!([0 2] 2)].push('?');
+    assert_eq!(v.as_array().unwrap()[r!([0 2] 2)], "c?");
+    v.as_mut_slice()[2].pop();
+
+    assert_eq!(v[r!(0)], "a");
+    assert_eq!(v.get(r!([0 2] 2)).map(String::as_str), Some("c"));
+    v[r!([1 2] 1)].push('!');
+    assert_eq!(v.as_slice(), ["a", "b!", "c"]);
+    assert_eq!(format!("{v:?}"), r#"["a", "b!", "c"]"#);
+    assert_eq!(v.clone(), v);
+
+    let (last, len) = v.pop().unwrap();
+    assert_eq!((last.as_str(), len.get()), ("c", 2));
+    assert!(v.get(r!([0 2] 2)).is_none());
+    assert!(v.as_array().is_none());
+    v.truncate(r!([] 1));
+    assert_eq!(v.iter().map(String::as_str).collect::<Vec<_>>(), ["a"]);
+    v.clear();
+    assert!(v.pop().is_none());
+
+    // The elements are dropped exactly once
+    let rc = std::rc::Rc::new(());
+    let mut v = BoundedVec::<_, 4>::default();
+    for _ in 0..4 {
+        v.push(rc.clone()).unwrap();
+    }
+    assert_eq!(std::rc::Rc::strong_count(&rc), 5);
+    v.truncate(r!([] 3));
+    assert_eq!(std::rc::Rc::strong_count(&rc), 4);
+    drop(v);
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+}
+
+#[test]
+#[should_panic(expected = "the len is 1 but the index is 1")]
+fn test_bounded_vec_index_len() {
+    let mut v = BoundedVec::<i32, 3>::new();
+    v.push(1).unwrap();
+    let _ = v[r!([0 2] 1)];
+}
+
+#[test]
+fn test_ranged_map() {
+    const TABLE: RangedMap<10, 12, char> = RangedMap::from_array(['a', 'b', 'c']);
+    const B: char = *TABLE.get(r!(11));
+    let mut m = RangedMap::<-3, 3, i32>::from_fn(|k| k.i32() * k.i32());
+    assert_eq!(m.as_array(), &[9, 4, 1, 0, 1, 4, 9]);
+    assert_eq!(m[r!(-3)], 9);
+    assert_eq!(*m.get(r!([-3 3] 2)), 4);
+    m[r!([-3 3] 0)] = 100;
+    *m.get_mut(r!(3)) += 1;
+    assert_eq!(m.into_array(), [9, 4, 1, 100, 1, 4, 10]);
+
+    let keys: Vec<i32> = m.iter().map(|(k, _)| k.i32()).collect();
+    assert_eq!(keys, [-3, -2, -1, 0, 1, 2, 3]);
+    assert_eq!(m.iter().len(), 7);
+    assert_eq!(m.iter().next_back().map(|(k, &v)| (k.i32(), v)), Some((3, 10)));
+    for (k, v) in &mut m {
+        *v = k.i32();
+    }
+    assert_eq!(m.map(|v| v * 2).as_array(), &[-6, -4, -2, 0, 2, 4, 6]);
+    assert_eq!(format!("{:?}", RangedMap::<1, 2, bool>::default()), "{1: false, 2: false}");
+    assert_eq!(B, 'b');
+}
+
+#[test]
+fn test_ranged_set() {
+    let mut s = RangedSet::<-10, 100>::new();
+    assert!(s.is_empty());
+    assert_eq!(s.len(), 0);
+    assert!(s.insert(r!(-10)));
+    assert!(s.insert(r!([-10 100] 53)));
+    assert!(s.insert(r!(54)));
+    assert!(s.insert(r!(100)));
+    assert!(!s.insert(r!(54)));
+    assert!(s.contains(r!([50 60] 54)));
+    assert!(!s.contains(r!(0)));
+    assert_eq!(s.len(), 4);
+    assert_eq!(s.iter().map(Ranged::i32).collect::<Vec<_>>(), [-10, 53, 54, 100]);
+    assert_eq!(s.iter().len(), 4);
+    assert_eq!(format!("{s:?}"), "{-10, 53, 54, 100}");
+    assert!(s.remove(r!(53)));
+    assert!(!s.remove(r!(53)));
+    assert_eq!(format!("{s:?}"), "{-10, 54, 100}");
+
+    let full = RangedSet::<-10, 100>::full();
+    assert_eq!(full.len(), 111);
+    assert_eq!((!s).len(), 108);
+    assert_eq!(!full, RangedSet::new());
+    assert_eq!(full.iter().last().unwrap(), 100);
+    assert!(s.is_subset(&full));
+    assert!(s.is_disjoint(&!s));
+
+    let a: RangedSet<1, 9> = [r!([1 9] 1), r!([] 2), r!([] 3)].into_iter().collect();
+    let b: RangedSet<1, 9> = [r!([1 9] 3), r!([] 4)].into_iter().collect();
+    assert_eq!(format!("{:?}", a | b), "{1, 2, 3, 4}");
+    assert_eq!(format!("{:?}", a & b), "{3}");
+    assert_eq!(format!("{:?}", a - b), "{1, 2}");
+    assert_eq!(format!("{:?}", a ^ b), "{1, 2, 4}");
+    let mut c = a;
+    c |= b;
+    c -= RangedSet::full() - a;
+    assert_eq!(c, a);
+    c.clear();
+    assert!(c.is_empty());
+
+    assert_eq!(size_of::<RangedSet<0, 63>>(), 8);
+    assert_eq!(size_of::<RangedSet<0, 64>>(), 16);
+    assert_eq!(RangedSet::<0, 63>::full().len(), 64);
+}
+
+#[test]
+fn test_ranged_set_first_last() {
+    let mut s = RangedSet::<-100, 100>::new();
+    assert!(s.first().is_none() && s.last().is_none());
+    s.insert(r!(-37));
+    s.insert(r!(64));
+    s.insert(r!(99));
+    assert_eq!(s.first().unwrap(), -37);
+    assert_eq!(s.last().unwrap(), 99);
+    assert_eq!(RangedSet::<0, 127>::full().last().unwrap(), 127);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_bucket_queue() {
+    let mut q = BucketQueue::<-5, 70, String>::new();
+    assert!(q.is_empty());
+    assert!(q.pop_min().is_none());
+    for (p, s) in [(r!([-5 70] 3), "a"), (r!([] -5), "b"), (r!([] 70), "c"), (r!([] 3), "d"), (r!([] 65), "e")] {
+        q.push(p, s.into());
+    }
+    q.push(r!(3), "f".into());
+    assert_eq!(q.len(), 6);
+    assert_eq!(format!("{q:?}"), r#"{-5: ["b"], 3: ["a", "d", "f"], 65: ["e"], 70: ["c"]}"#);
+
+    assert_eq!(q.peek_min().map(|(p, s)| (p.get(), s.as_str())), Some((-5, "b")));
+    assert_eq!(q.peek_max().map(|(p, s)| (p.get(), s.as_str())), Some((70, "c")));
+    let order: Vec<(i32, String)> = core::iter::from_fn(|| q.pop_min()).map(|(p, s)| (p.i32(), s)).collect();
+    assert_eq!(order, [(-5, "b".into()), (3, "a".into()), (3, "d".into()), (3, "f".into()), (65, "e".into()), (70, "c".into())]);
+    assert!(q.is_empty());
+
+    q.push(r!(0), "x".into());
+    q.push(r!(1), "y".into());
+    assert_eq!(q.pop_max().map(|(p, s)| (p.get(), s)), Some((1, "y".into())));
+    q.clear();
+    assert!(q.pop_max().is_none());
+    assert_eq!(q.len(), 0);
+
+    // The buckets of the wide ranges are not on the stack
+    let mut q = BucketQueue::<0, 65535, u32>::new();
+    q.push(r!(65535), 1);
+    q.push(r!(0), 2);
+    q.push(r!(40000), 3);
+    assert_eq!(q.pop_max().map(|(p, x)| (p.get(), x)), Some((65535, 1)));
+    assert_eq!(q.pop_min().map(|(p, x)| (p.get(), x)), Some((0, 2)));
+    assert_eq!(q.peek_min().map(|(p, &x)| (p.get(), x)), Some((40000, 3)));
+
+    // The cached lowest and highest priorities follow the pushes and the pops
+    let mut state = 99_u64;
+    let mut next = move || {
+        state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        state >> 33
+    };
+    let mut q = BucketQueue::<-100, 200, u64>::new();
+    let mut reference = std::collections::BTreeMap::<i128, std::collections::VecDeque<u64>>::new();
+    for item in 0..3000 {
+        match next() % 3 {
+            0 => {
+                let p = i128::from(next() % 301) - 100;
+                q.push(Ranged::<-100, 200>::new(p).unwrap(), item);
+                reference.entry(p).or_default().push_back(item);
+            }
+            1 => {
+                let expected = reference.first_entry().map(|mut e| {
+                    let x = e.get_mut().pop_front().unwrap();
+                    let p = *e.key();
+                    if e.get().is_empty() { e.remove(); }
+                    (p, x)
+                });
+                assert_eq!(q.pop_min().map(|(p, x)| (p.get(), x)), expected);
+            }
+            _ => {
+                let expected = reference.last_entry().map(|mut e| {
+                    let x = e.get_mut().pop_front().unwrap();
+                    let p = *e.key();
+                    if e.get().is_empty() { e.remove(); }
+                    (p, x)
+                });
+                assert_eq!(q.pop_max().map(|(p, x)| (p.get(), x)), expected);
+            }
+        }
+        assert_eq!(q.peek_min().map(|(p, &x)| (p.get(), x)), reference.first_key_value().map(|(&p, b)| (p, b[0])));
+        assert_eq!(q.peek_max().map(|(p, &x)| (p.get(), x)), reference.last_key_value().map(|(&p, b)| (p, b[0])));
+        assert_eq!(q.len(), reference.values().map(std::collections::VecDeque::len).sum::<usize>());
+    }
+}
+
+#[test]
+fn test_permutation() {
+    let id = Permutation::<5>::identity();
+    assert!(id.is_identity());
+    assert_eq!(id.cycles().count(), 5);
+
+    let p = Permutation::new([r!([0 4] 3), r!([] 0), r!([] 4), r!([] 1), r!([] 2)]).unwrap();
+    let q = Permutation::new([r!([0 4] 1), r!([] 2), r!([] 0), r!([] 4), r!([] 3)]).unwrap();
+    assert!(!p.is_identity());
+    assert_eq!(format!("{p:?}"), "Permutation[3, 0, 4, 1, 2]");
+    assert_eq!(p.get(r!([0 4] 2)), 4);
+    assert!(Permutation::new([r!([0 4] 3), r!([] 0), r!([] 4), r!([] 1), r!([] 3)]).is_none());
+
+    // Non-Copy elements are moved exactly once
+    let words = ["a", "b", "c", "d", "e"].map(String::from);
+    assert_eq!(p.apply(words.clone()), ["d", "a", "e", "b", "c"]);
+    assert_eq!(p.inverse().apply(p.apply(words.clone())), words);
+    assert_eq!(p.compose(&q).apply(words.clone()), q.apply(p.apply(words)));
+    assert_eq!(p.compose(&q).get(r!([0 4] 0)), p.get(q.get(r!([0 4] 0))));
+    assert!(p.inverse().compose(&p).is_identity());
+    assert_eq!(p.inverse().inverse(), p);
+
+    let cycles: Vec<Vec<i32>> = p.cycles().map(|c| c.map(Ranged::i32).collect()).collect();
+    assert_eq!(cycles, [vec![0, 3, 1], vec![2, 4]]);
+    let cycles: Vec<Vec<i32>> = q.cycles().map(|c| c.map(Ranged::i32).collect()).collect();
+    assert_eq!(cycles, [vec![0, 1, 2], vec![3, 4]]);
+
+    let single = Permutation::<1>::identity();
+    assert_eq!(single.apply([7]), [7]);
+    assert_eq!(single.cycles().count(), 1);
 }
 
+#[test]
+fn test_ring_buffer() {
+    let mut buf = RingBuffer::<String, 4>::new();
+    assert!(buf.is_empty());
+    assert!(buf.pop_front().is_none() && buf.pop_back().is_none());
+    assert!(buf.front().is_none() && buf.back().is_none());
+    assert_eq!(buf.capacity(), 4);
+
+    for s in ["a", "b", "c"] {
+        buf.push_back(s.into()).unwrap();
+    }
+    buf.push_front("z".into()).unwrap();
+    assert!(buf.is_full());
+    assert_eq!(buf.push_back("x".into()), Err("x".into()));
+    assert_eq!(format!("{buf:?}"), r#"["z", "a", "b", "c"]"#);
+    assert_eq!(buf.head(), 3);
+    assert_eq!(buf.tail(), 3);
+
+    // Wrap around the storage several times
+    for s in ["d", "e", "f", "g", "h"] {
+        let evicted = buf.push_overwrite(s.into());
+        assert!(evicted.is_some());
+    }
+    assert_eq!(buf.len(), 4);
+    assert_eq!(buf[r!([0 3] 0)], "e");
+    assert_eq!(buf.get(r!([0 3] 3)).unwrap(), "h");
+    assert_eq!(buf.iter().rev().cloned().collect::<Vec<_>>(), ["h", "g", "f", "e"]);
+    assert_eq!(buf.iter().len(), 4);
+
+    buf[r!([0 3] 1)].push('!');
+    assert_eq!(buf.pop_back().unwrap(), "h");
+    assert_eq!(buf.pop_front().unwrap(), "e");
+    assert_eq!(buf.len(), 2);
+    assert!(buf.get(r!([0 3] 2)).is_none());
+    assert_eq!(buf.clone(), buf);
+    assert_eq!(buf.front().unwrap(), "f!");
+    assert_eq!(buf.back().unwrap(), "g");
+
+    buf.clear();
+    assert!(buf.is_empty());
+    assert_eq!(buf.tail(), 0);
+}
+
+#[test]
+#[should_panic(expected = "index out of bounds: the len is 1 but the index is 2")]
+fn test_ring_buffer_index_len() {
+    let mut buf = RingBuffer::<u8, 3>::new();
+    buf.push_back(1).unwrap();
+    let _ = buf[r!([0 2] 2)];
+}
+
+#[test]
+fn test_sort_ranged() {
+    // A simple LCG, so the test is deterministic
+    let mut state = 12345_u64;
+    let mut next = move || {
+        state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33).cast_signed()
+    };
+
+    let mut narrow: Vec<Ranged<-10, 200>> = (0..1000).map(|_| Ranged::new(i128::from(next() % 211 - 10)).unwrap()).collect();
+    let mut expected = narrow.clone();
+    expected.sort_unstable();
+    sort_ranged(&mut narrow);
+    assert_eq!(narrow, expected);
+
+    let mut wide: Vec<Ranged<-1_000_000, 70_000>> = (0..1000).map(|_| Ranged::new(i128::from(next() % 1_070_001 - 1_000_000)).unwrap()).collect();
+    let mut expected = wide.clone();
+    expected.sort_unstable();
+    sort_ranged(&mut wide);
+    assert_eq!(wide, expected);
+
+    let mut full: Vec<Ranged<{i64::MIN as irang}, {i64::MAX as irang}>> = (0..1000).map(|_| Ranged::new(i128::from(next().wrapping_mul(next()))).unwrap()).collect();
+    let mut expected = full.clone();
+    expected.sort_unstable();
+    sort_ranged(&mut full);
+    assert_eq!(full, expected);
+
+    let mut single = [r!(5), r!(5)];
+    sort_ranged(&mut single);
+    sort_ranged::<0, 1>(&mut []);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_sort_by_ranged_key() {
+    let mut state = 777_u64;
+    let mut next = move || {
+        state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33).cast_signed()
+    };
+
+    // Compare with the stable std sort, the index reveals the order of the equal keys
+    for modulo in [7, 1000, 100_000] {
+        let mut items: Vec<(i64, String)> = (0..500).map(|i| (next() % modulo, format!("item{i}"))).collect();
+        let mut expected = items.clone();
+        expected.sort_by_key(|x| x.0);
+        sort_by_ranged_key(&mut items, |x| Ranged::<0, 100_000>::new(i128::from(x.0)).unwrap());
+        assert_eq!(items, expected);
+    }
+
+    let mut items = [(3, 'a'), (-3, 'b'), (3, 'c'), (0, 'd'), (-3, 'e')];
+    sort_by_ranged_key(&mut items, |x| Ranged::<-3, 3>::new(x.0).unwrap());
+    assert_eq!(items, [(-3, 'b'), (-3, 'e'), (0, 'd'), (3, 'a'), (3, 'c')]);
+}
+
+#[test]
+fn test_const_tables() {
+    const fn cube(x: Ranged<-3, 3>) -> i32 {
+        let x = x.i32();
+        x * x * x
+    }
+    const fn bits(i: Ranged<0, 15>) -> u32 {
+        i.get().count_ones()
+    }
+    const CUBES: RangedTable<-3, 3, i32> = RangedTable::from_const_fn(cube);
+    const BITS: [u32; 16] = array_from_fn(bits);
+
+    assert_eq!(CUBES.as_array(), &[-27, -8, -1, 0, 1, 8, 27]);
+    assert_eq!(CUBES[r!([-3 3] -2)], -8);
+    assert_eq!(BITS[r!([0 15] 11)], 3);
+    assert_eq!(BITS.iter().sum::<u32>(), 32);
+
+    // Not in the constant context, the stateful closures and the non-Copy values are accepted
+    let mut calls = 0;
+    let names: RangedTable<1, 3, String> = RangedTable::from_const_fn(|k| { calls += 1; format!("#{k}") });
+    assert_eq!(calls, 3);
+    assert_eq!(names[r!([1 3] 3)], "#3");
+    let names: [String; 3] = array_from_fn(|i: Ranged<0, 2>| names[i.add(r!(1))].clone());
+    assert_eq!(names, ["#1", "#2", "#3"]);
+
+    // The elements created before a panic are dropped
+    let rc = std::rc::Rc::new(());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let _: [_; 5] = array_from_fn(|i: Ranged<0, 4>| { assert!(i != 3, "key 3"); rc.clone() });
+    }));
+    assert!(result.is_err());
+    assert_eq!(std::rc::Rc::strong_count(&rc), 1);
+}